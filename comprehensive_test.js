@@ -124,6 +124,7 @@ async function runTests() {
         });
 
         let ata = await getAssociatedTokenAddress(mintKeypair.publicKey, destinationKeypair.publicKey);
+        let sourceAta = await getAssociatedTokenAddress(mintKeypair.publicKey, ownerKeypair.publicKey);
 
         test("Token transfer should be successful", () => {
             if (tokenRes.status !== 200) throw new Error(`Expected 200, got ${tokenRes.status}`);
@@ -132,15 +133,16 @@ async function runTests() {
             if (tokenRes.data.data.accounts.length !== 3) throw new Error("Expected 3 accounts");
         });
 
-        test("Token transfer account structure should match test expectations", () => {
+        test("Token transfer account structure should match the instruction", () => {
             const accounts = tokenRes.data.data.accounts;
-            if (accounts[0].pubkey !== ownerKeypair.publicKey.toString()) throw new Error("accounts[0] should be owner");
+            if (accounts[0].pubkey !== sourceAta.toString()) throw new Error("accounts[0] should be source ATA");
             if (accounts[1].pubkey !== ata.toString()) throw new Error("accounts[1] should be destination ATA");
             if (accounts[2].pubkey !== ownerKeypair.publicKey.toString()) throw new Error("accounts[2] should be owner");
-            
-            if (accounts[0].isSigner !== false) throw new Error("accounts[0].isSigner should be false");
-            if (accounts[1].isSigner !== false) throw new Error("accounts[1].isSigner should be false");
-            if (accounts[2].isSigner !== true) throw new Error("accounts[2].isSigner should be true");
+
+            if (accounts[0].role !== "source" || accounts[1].role !== "destination" || accounts[2].role !== "owner") throw new Error("Unexpected account roles");
+            if (accounts[0].is_signer !== false || accounts[0].is_writable !== true) throw new Error("accounts[0] should be writable, not signer");
+            if (accounts[1].is_signer !== false || accounts[1].is_writable !== true) throw new Error("accounts[1] should be writable, not signer");
+            if (accounts[2].is_signer !== true || accounts[2].is_writable !== false) throw new Error("accounts[2] should be a read-only signer");
         });
 
         // Test 7: Token creation
@@ -0,0 +1,182 @@
+use axum::{extract::Json, http::StatusCode, response::Json as ResponseJson};
+use solana_program::pubkey::{MAX_SEED_LEN, MAX_SEEDS, Pubkey};
+
+use crate::models::{
+    AddressInspectionData, ApiResponse, AssociatedTokenAccountData, DerivePdaRequest, InspectAddressRequest, PdaData,
+    PdaSeedSpec,
+};
+use crate::utils::{decode_hex, encode_hex, parse_pubkey, result_to_response};
+
+const MAX_INSPECTED_MINTS: usize = 25;
+
+fn pda_seed_to_bytes(seed_spec: &PdaSeedSpec) -> Result<Vec<u8>, String> {
+    match seed_spec {
+        PdaSeedSpec::Utf8(seed_text) => Ok(seed_text.as_bytes().to_vec()),
+        PdaSeedSpec::Pubkey(seed_address) => Ok(parse_pubkey(seed_address)?.to_bytes().to_vec()),
+        PdaSeedSpec::U8(seed_value) => Ok(vec![*seed_value]),
+        PdaSeedSpec::U16(seed_value) => Ok(seed_value.to_le_bytes().to_vec()),
+        PdaSeedSpec::U32(seed_value) => Ok(seed_value.to_le_bytes().to_vec()),
+        PdaSeedSpec::U64(seed_value) => Ok(seed_value.to_le_bytes().to_vec()),
+        PdaSeedSpec::Hex(seed_hex) => decode_hex(seed_hex),
+    }
+}
+
+pub fn derive_pda(pda_request: &DerivePdaRequest) -> Result<PdaData, String> {
+    let program_address = match &pda_request.program_id {
+        Some(program_address) if !program_address.is_empty() => program_address,
+        _ => return Err("Please provide the program id the address is derived for".to_string()),
+    };
+
+    let seed_specs = match &pda_request.seeds {
+        Some(seed_specs) if seed_specs.len() >= MAX_SEEDS => {
+            return Err(format!("At most {} seeds can be used, since the bump takes the last of the {} seed slots", MAX_SEEDS - 1, MAX_SEEDS));
+        }
+        Some(seed_specs) => seed_specs,
+        None => return Err("Please provide the list of seeds, which may be empty".to_string()),
+    };
+
+    let program_id = parse_pubkey(program_address)?;
+
+    let mut seed_bytes = Vec::with_capacity(seed_specs.len());
+    for (seed_index, seed_spec) in seed_specs.iter().enumerate() {
+        let encoded_seed = pda_seed_to_bytes(seed_spec).map_err(|seed_error| format!("Seed {}: {}", seed_index, seed_error))?;
+        if encoded_seed.len() > MAX_SEED_LEN {
+            return Err(format!("Seed {}: seeds can be at most {} bytes, but this one is {} bytes", seed_index, MAX_SEED_LEN, encoded_seed.len()));
+        }
+        seed_bytes.push(encoded_seed);
+    }
+
+    let seed_refs: Vec<&[u8]> = seed_bytes.iter().map(|seed| seed.as_slice()).collect();
+    let (canonical_address, canonical_bump) = Pubkey::try_find_program_address(&seed_refs, &program_id)
+        .ok_or_else(|| "No valid program address exists for these seeds".to_string())?;
+
+    let (program_address, bump) = match pda_request.bump {
+        Some(requested_bump) => {
+            let bump_seed = [requested_bump];
+            let mut bumped_seed_refs = seed_refs.clone();
+            bumped_seed_refs.push(&bump_seed);
+            let created_address = Pubkey::create_program_address(&bumped_seed_refs, &program_id).map_err(|_| {
+                format!("The seeds with bump {} produce an address on the ed25519 curve, which is not a valid program address", requested_bump)
+            })?;
+            (created_address, requested_bump)
+        }
+        None => (canonical_address, canonical_bump),
+    };
+
+    let matches_address = pda_request
+        .address
+        .as_deref()
+        .map(|expected_address| parse_pubkey(expected_address).map(|expected_public_key| expected_public_key == program_address))
+        .transpose()?;
+
+    Ok(PdaData {
+        address: program_address.to_string(),
+        bump,
+        program_id: program_id.to_string(),
+        seeds: seed_bytes.iter().map(|seed| encode_hex(seed)).collect(),
+        canonical_bump: bump == canonical_bump,
+        matches_address,
+    })
+}
+
+pub async fn derive_program_address(
+    Json(pda_request): Json<DerivePdaRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<PdaData>>) {
+    result_to_response(derive_pda(&pda_request))
+}
+
+fn known_program_label(public_key: &Pubkey) -> Option<&'static str> {
+    let known_programs = [
+        (solana_program::system_program::id(), "System Program"),
+        (spl_token::id(), "SPL Token Program"),
+        (spl_token_2022::id(), "Token-2022 Program"),
+        (spl_associated_token_account::id(), "Associated Token Account Program"),
+        (spl_memo::id(), "Memo Program"),
+        (spl_memo::v1::id(), "Memo Program (v1)"),
+        (solana_sdk::compute_budget::id(), "Compute Budget Program"),
+        (solana_program::stake::program::id(), "Stake Program"),
+        (solana_program::vote::program::id(), "Vote Program"),
+        (solana_program::config::program::id(), "Config Program"),
+        (solana_program::address_lookup_table::program::id(), "Address Lookup Table Program"),
+        (solana_program::bpf_loader::id(), "BPF Loader"),
+        (solana_program::bpf_loader_deprecated::id(), "BPF Loader (deprecated)"),
+        (solana_program::bpf_loader_upgradeable::id(), "BPF Upgradeable Loader"),
+        (solana_program::loader_v4::id(), "Loader v4"),
+        (solana_program::ed25519_program::id(), "Ed25519 Signature Verification Program"),
+        (solana_program::secp256k1_program::id(), "Secp256k1 Signature Verification Program"),
+        (solana_program::sysvar::clock::id(), "Clock Sysvar"),
+        (solana_program::sysvar::rent::id(), "Rent Sysvar"),
+        (solana_program::sysvar::epoch_schedule::id(), "Epoch Schedule Sysvar"),
+        (solana_program::sysvar::instructions::id(), "Instructions Sysvar"),
+        (solana_program::sysvar::slot_hashes::id(), "Slot Hashes Sysvar"),
+        (solana_program::sysvar::stake_history::id(), "Stake History Sysvar"),
+    ];
+
+    known_programs
+        .iter()
+        .find(|(program_id, _)| program_id == public_key)
+        .map(|(_, program_label)| *program_label)
+}
+
+pub fn build_address_inspection(inspect_request: &InspectAddressRequest) -> Result<AddressInspectionData, String> {
+    let inspected_address = match &inspect_request.address {
+        Some(address) if !address.is_empty() => address,
+        _ => return Err("Please provide the address to inspect".to_string()),
+    };
+
+    let mint_addresses = match &inspect_request.mints {
+        Some(mint_addresses) if mint_addresses.len() > MAX_INSPECTED_MINTS => {
+            return Err(format!("At most {} mints can be inspected at once", MAX_INSPECTED_MINTS));
+        }
+        Some(mint_addresses) => mint_addresses.as_slice(),
+        None => &[],
+    };
+
+    let public_key = parse_pubkey(inspected_address)?;
+    let on_curve = public_key.is_on_curve();
+    let known_program = known_program_label(&public_key);
+
+    let mut warnings = Vec::new();
+    if let Some(program_label) = known_program {
+        warnings.push(format!("This is the {}, not a wallet, so tokens sent to it cannot be recovered", program_label));
+    } else if !on_curve {
+        warnings.push("This address is off the ed25519 curve, so only the program that derived it can sign for it".to_string());
+    }
+
+    let mut associated_token_accounts = Vec::with_capacity(mint_addresses.len() * 2);
+    for (mint_index, mint_address) in mint_addresses.iter().enumerate() {
+        let mint_public_key = parse_pubkey(mint_address).map_err(|mint_error| format!("Mint {}: {}", mint_index, mint_error))?;
+        if mint_public_key == public_key {
+            warnings.push(format!("Mint {}: this address is the mint itself, not a token owner", mint_index));
+        }
+
+        for (token_program_name, token_program_id) in [("spl-token", spl_token::id()), ("token-2022", spl_token_2022::id())] {
+            let token_account = spl_associated_token_account::get_associated_token_address_with_program_id(&public_key, &mint_public_key, &token_program_id);
+            associated_token_accounts.push(AssociatedTokenAccountData {
+                mint: mint_public_key.to_string(),
+                token_program: token_program_name.to_string(),
+                address: token_account.to_string(),
+            });
+        }
+    }
+
+    Ok(AddressInspectionData {
+        address: public_key.to_string(),
+        on_curve,
+        address_type: match (known_program, on_curve) {
+            (Some(_), _) => "program",
+            (None, true) => "wallet",
+            (None, false) => "off-curve",
+        }
+        .to_string(),
+        known_program: known_program.map(str::to_string),
+        associated_token_accounts,
+        warnings,
+    })
+}
+
+pub async fn inspect_address(
+    Json(inspect_request): Json<InspectAddressRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<AddressInspectionData>>) {
+    result_to_response(build_address_inspection(&inspect_request))
+}
@@ -0,0 +1,446 @@
+use axum::{extract::Json, http::StatusCode, response::Json as ResponseJson};
+use solana_program::{instruction::Instruction, message::Message, pubkey::Pubkey};
+use solana_sdk::{packet::PACKET_DATA_SIZE, transaction::Transaction};
+
+use crate::handlers::{build_sol_transfer_instructions, build_token_transfer_instructions};
+use crate::models::{
+    ApiResponse, BatchRecipientError, BatchTransactionData, BatchTransferData, ComputeBudgetSpec, SendSolBatchRequest,
+    SendSolRequest, SendTokenBatchRequest, SendTokenRequest,
+};
+use crate::utils::{
+    instruction_to_response, parse_multisig_signers, parse_optional_pubkey, parse_pubkey, parse_token_decimals, parse_token_program,
+    prepend_compute_budget_instructions, result_to_response,
+};
+
+const MAX_BATCH_RECIPIENTS: usize = 1000;
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+struct RecipientInstructions {
+    index: usize,
+    recipient: Option<String>,
+    instructions: Vec<Instruction>,
+}
+
+fn unsigned_transaction_size(fee_payer_public_key: &Pubkey, transaction_instructions: &[Instruction]) -> usize {
+    let unsigned_transaction = Transaction::new_unsigned(Message::new(transaction_instructions, Some(fee_payer_public_key)));
+    bincode::serialized_size(&unsigned_transaction).map_or(usize::MAX, |size| size as usize)
+}
+
+fn budgeted_transaction_size(
+    fee_payer_public_key: &Pubkey,
+    transaction_instructions: &[Instruction],
+    compute_budget: &ComputeBudgetSpec,
+) -> Result<usize, String> {
+    let (budgeted_instructions, _) = prepend_compute_budget_instructions(transaction_instructions.to_vec(), compute_budget)?;
+    Ok(unsigned_transaction_size(fee_payer_public_key, &budgeted_instructions))
+}
+
+fn finish_batch_transaction(
+    fee_payer_public_key: &Pubkey,
+    transaction_instructions: Vec<Instruction>,
+    recipient_indexes: Vec<usize>,
+    compute_budget: &ComputeBudgetSpec,
+) -> Result<BatchTransactionData, String> {
+    let (budgeted_instructions, priority_fee_lamports) = prepend_compute_budget_instructions(transaction_instructions, compute_budget)?;
+    let transaction_message = Message::new(&budgeted_instructions, Some(fee_payer_public_key));
+    let required_signature_count = transaction_message.header.num_required_signatures as usize;
+    let required_signers = transaction_message.account_keys[..required_signature_count]
+        .iter()
+        .map(|signer_key| signer_key.to_string())
+        .collect();
+
+    Ok(BatchTransactionData {
+        size_bytes: unsigned_transaction_size(fee_payer_public_key, &budgeted_instructions),
+        instructions: budgeted_instructions.into_iter().map(instruction_to_response).collect(),
+        recipients: recipient_indexes,
+        required_signers,
+        estimated_fee_lamports: required_signature_count as u64 * LAMPORTS_PER_SIGNATURE + priority_fee_lamports.unwrap_or(0),
+        priority_fee_lamports,
+    })
+}
+
+// Greedily fills each transaction in request order, starting a new one whenever the next recipient would overflow the packet
+fn pack_recipient_instructions(
+    fee_payer_public_key: &Pubkey,
+    recipient_instructions: Vec<RecipientInstructions>,
+    compute_budget: &ComputeBudgetSpec,
+    recipient_errors: &mut Vec<BatchRecipientError>,
+) -> Result<Vec<BatchTransactionData>, String> {
+    let mut packed_transactions = Vec::new();
+    let mut pending_instructions: Vec<Instruction> = Vec::new();
+    let mut pending_recipients: Vec<usize> = Vec::new();
+
+    for recipient in recipient_instructions {
+        let mut candidate_instructions = pending_instructions.clone();
+        candidate_instructions.extend(recipient.instructions.iter().cloned());
+        if budgeted_transaction_size(fee_payer_public_key, &candidate_instructions, compute_budget)? <= PACKET_DATA_SIZE {
+            pending_instructions = candidate_instructions;
+            pending_recipients.push(recipient.index);
+            continue;
+        }
+
+        if budgeted_transaction_size(fee_payer_public_key, &recipient.instructions, compute_budget)? > PACKET_DATA_SIZE {
+            recipient_errors.push(BatchRecipientError {
+                index: recipient.index,
+                recipient: recipient.recipient,
+                error: "The instructions for this recipient do not fit in a single transaction".to_string(),
+            });
+            continue;
+        }
+
+        if !pending_instructions.is_empty() {
+            packed_transactions.push(finish_batch_transaction(
+                fee_payer_public_key,
+                std::mem::take(&mut pending_instructions),
+                std::mem::take(&mut pending_recipients),
+                compute_budget,
+            )?);
+        }
+        pending_instructions = recipient.instructions;
+        pending_recipients.push(recipient.index);
+    }
+
+    if !pending_instructions.is_empty() {
+        packed_transactions.push(finish_batch_transaction(fee_payer_public_key, pending_instructions, pending_recipients, compute_budget)?);
+    }
+
+    Ok(packed_transactions)
+}
+
+pub fn build_sol_batch(batch_request: &SendSolBatchRequest) -> Result<BatchTransferData, String> {
+    let sender_wallet = match &batch_request.from {
+        Some(wallet_address) if !wallet_address.is_empty() => wallet_address,
+        _ => return Err("Please provide a valid sender wallet address".to_string()),
+    };
+
+    let batch_recipients = match &batch_request.recipients {
+        Some(recipients) if recipients.len() > MAX_BATCH_RECIPIENTS => {
+            return Err(format!("A batch can contain at most {} recipients", MAX_BATCH_RECIPIENTS));
+        }
+        Some(recipients) if !recipients.is_empty() => recipients,
+        _ => return Err("Please provide at least one recipient for the batch".to_string()),
+    };
+
+    let sender_public_key = parse_pubkey(sender_wallet).map_err(|_| "Invalid sender public key".to_string())?;
+    let fee_payer_public_key = parse_optional_pubkey(batch_request.fee_payer.as_deref())?.unwrap_or(sender_public_key);
+    if sender_public_key == solana_program::system_program::id() || fee_payer_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot send or pay for a batch".to_string());
+    }
+
+    let mut recipient_errors = Vec::new();
+    let mut recipient_instructions = Vec::new();
+    for (recipient_index, batch_recipient) in batch_recipients.iter().enumerate() {
+        let recipient_transfer_request = SendSolRequest {
+            from: Some(sender_wallet.clone()),
+            to: batch_recipient.to.clone(),
+            lamports: batch_recipient.lamports,
+            memo: None,
+            compute_budget: ComputeBudgetSpec::default(),
+        };
+
+        match build_sol_transfer_instructions(&recipient_transfer_request) {
+            Ok(transfer_instructions) => recipient_instructions.push(RecipientInstructions {
+                index: recipient_index,
+                recipient: batch_recipient.to.clone(),
+                instructions: transfer_instructions,
+            }),
+            Err(validation_error) => recipient_errors.push(BatchRecipientError {
+                index: recipient_index,
+                recipient: batch_recipient.to.clone(),
+                error: validation_error,
+            }),
+        }
+    }
+
+    let packed_transactions = pack_recipient_instructions(&fee_payer_public_key, recipient_instructions, &batch_request.compute_budget, &mut recipient_errors)?;
+    recipient_errors.sort_by_key(|recipient_error| recipient_error.index);
+
+    Ok(BatchTransferData {
+        transactions: packed_transactions,
+        errors: recipient_errors,
+    })
+}
+
+pub async fn send_sol_batch(
+    Json(batch_request): Json<SendSolBatchRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<BatchTransferData>>) {
+    result_to_response(build_sol_batch(&batch_request))
+}
+
+pub fn build_token_batch(batch_request: &SendTokenBatchRequest) -> Result<BatchTransferData, String> {
+    let token_mint_address = match &batch_request.mint {
+        Some(mint_address) if !mint_address.is_empty() => mint_address,
+        _ => return Err("Token mint address must be specified".to_string()),
+    };
+
+    let current_token_owner = match &batch_request.owner {
+        Some(owner_address) if !owner_address.is_empty() => owner_address,
+        _ => return Err("Current token owner address is needed".to_string()),
+    };
+
+    let batch_recipients = match &batch_request.recipients {
+        Some(recipients) if recipients.len() > MAX_BATCH_RECIPIENTS => {
+            return Err(format!("A batch can contain at most {} recipients", MAX_BATCH_RECIPIENTS));
+        }
+        Some(recipients) if !recipients.is_empty() => recipients,
+        _ => return Err("Please provide at least one recipient for the batch".to_string()),
+    };
+
+    // Settings shared by every transfer are checked once up front so they fail the batch rather than each recipient
+    parse_token_program(batch_request.program.as_deref())?;
+    parse_token_decimals(batch_request.decimals)?;
+    let multisig_signers = parse_multisig_signers(batch_request.multisig_signers.as_deref())?;
+    parse_pubkey(token_mint_address)?;
+    let owner_public_key = parse_pubkey(current_token_owner)?;
+    let payer_public_key = parse_optional_pubkey(batch_request.payer.as_deref())?;
+    let requested_fee_payer_public_key = parse_optional_pubkey(batch_request.fee_payer.as_deref())?;
+
+    // A multisig owner is not a signer itself, so it can pay neither for new token accounts nor for the transactions
+    if !multisig_signers.is_empty() && batch_request.create_destination_ata.unwrap_or(false) && payer_public_key.is_none() {
+        return Err("A multisig owner cannot sign to pay for the destination token accounts, so please provide a payer".to_string());
+    }
+    if !multisig_signers.is_empty() && requested_fee_payer_public_key.is_none() {
+        return Err("A multisig owner cannot sign to pay the transaction fees, so please provide a feePayer".to_string());
+    }
+
+    let fee_payer_public_key = requested_fee_payer_public_key.unwrap_or(owner_public_key);
+    if fee_payer_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot pay for a batch".to_string());
+    }
+
+    let mut recipient_errors = Vec::new();
+    let mut recipient_instructions = Vec::new();
+    for (recipient_index, batch_recipient) in batch_recipients.iter().enumerate() {
+        let recipient_transfer_request = SendTokenRequest {
+            destination: batch_recipient.destination.clone(),
+            mint: batch_request.mint.clone(),
+            owner: batch_request.owner.clone(),
+            amount: batch_recipient.amount.clone(),
+            decimals: batch_request.decimals,
+            program: batch_request.program.clone(),
+            create_destination_ata: batch_request.create_destination_ata,
+            payer: batch_request.payer.clone(),
+            multisig_signers: batch_request.multisig_signers.clone(),
+            memo: None,
+            compute_budget: ComputeBudgetSpec::default(),
+        };
+
+        match build_token_transfer_instructions(&recipient_transfer_request) {
+            Ok(transfer_instructions) => recipient_instructions.push(RecipientInstructions {
+                index: recipient_index,
+                recipient: batch_recipient.destination.clone(),
+                instructions: transfer_instructions,
+            }),
+            Err(validation_error) => recipient_errors.push(BatchRecipientError {
+                index: recipient_index,
+                recipient: batch_recipient.destination.clone(),
+                error: validation_error,
+            }),
+        }
+    }
+
+    let packed_transactions = pack_recipient_instructions(&fee_payer_public_key, recipient_instructions, &batch_request.compute_budget, &mut recipient_errors)?;
+    recipient_errors.sort_by_key(|recipient_error| recipient_error.index);
+
+    Ok(BatchTransferData {
+        transactions: packed_transactions,
+        errors: recipient_errors,
+    })
+}
+
+pub async fn send_token_batch(
+    Json(batch_request): Json<SendTokenBatchRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<BatchTransferData>>) {
+    result_to_response(build_token_batch(&batch_request))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sol_batch_request(batch_json: serde_json::Value) -> SendSolBatchRequest {
+        serde_json::from_value(batch_json).unwrap()
+    }
+
+    fn sol_recipients(recipient_count: usize) -> Vec<serde_json::Value> {
+        (0..recipient_count)
+            .map(|_| json!({ "to": Pubkey::new_unique().to_string(), "lamports": 1_000 }))
+            .collect()
+    }
+
+    fn packed_recipients(batch_data: &BatchTransferData) -> Vec<usize> {
+        batch_data.transactions.iter().flat_map(|batch_transaction| batch_transaction.recipients.iter().copied()).collect()
+    }
+
+    #[test]
+    fn sol_batch_splits_recipients_across_packet_sized_transactions() {
+        let batch_data = build_sol_batch(&sol_batch_request(json!({
+            "from": Pubkey::new_unique().to_string(),
+            "recipients": sol_recipients(60),
+        })))
+        .unwrap();
+
+        assert!(batch_data.transactions.len() > 1);
+        assert!(batch_data.errors.is_empty());
+        assert_eq!(packed_recipients(&batch_data), (0..60).collect::<Vec<_>>());
+        for batch_transaction in &batch_data.transactions {
+            assert!(batch_transaction.size_bytes <= PACKET_DATA_SIZE);
+            assert_eq!(batch_transaction.instructions.len(), batch_transaction.recipients.len());
+            assert_eq!(batch_transaction.required_signers.len(), 1);
+            assert_eq!(batch_transaction.estimated_fee_lamports, LAMPORTS_PER_SIGNATURE);
+            assert_eq!(batch_transaction.priority_fee_lamports, None);
+        }
+    }
+
+    #[test]
+    fn sol_batch_reports_invalid_recipients_and_packs_the_rest() {
+        let mut recipients = sol_recipients(3);
+        recipients[1] = json!({ "to": "not-a-wallet", "lamports": 1_000 });
+        recipients.push(json!({ "to": Pubkey::new_unique().to_string(), "lamports": 0 }));
+
+        let batch_data = build_sol_batch(&sol_batch_request(json!({
+            "from": Pubkey::new_unique().to_string(),
+            "recipients": recipients,
+        })))
+        .unwrap();
+
+        assert_eq!(packed_recipients(&batch_data), vec![0, 2]);
+        let error_indexes: Vec<usize> = batch_data.errors.iter().map(|recipient_error| recipient_error.index).collect();
+        assert_eq!(error_indexes, vec![1, 3]);
+        assert_eq!(batch_data.errors[0].recipient.as_deref(), Some("not-a-wallet"));
+    }
+
+    #[test]
+    fn sol_batch_budgets_every_transaction_and_counts_the_fee_payer() {
+        let fee_payer = Pubkey::new_unique();
+        let batch_data = build_sol_batch(&sol_batch_request(json!({
+            "from": Pubkey::new_unique().to_string(),
+            "feePayer": fee_payer.to_string(),
+            "recipients": sol_recipients(60),
+            "computeUnitLimit": 200_000,
+            "computeUnitPriceMicroLamports": 50_000,
+        })))
+        .unwrap();
+
+        assert_eq!(packed_recipients(&batch_data), (0..60).collect::<Vec<_>>());
+        for batch_transaction in &batch_data.transactions {
+            assert!(batch_transaction.size_bytes <= PACKET_DATA_SIZE);
+            assert_eq!(batch_transaction.instructions[0].program_id, solana_sdk::compute_budget::id().to_string());
+            assert_eq!(batch_transaction.instructions[1].program_id, solana_sdk::compute_budget::id().to_string());
+            assert_eq!(batch_transaction.required_signers[0], fee_payer.to_string());
+            assert_eq!(batch_transaction.required_signers.len(), 2);
+            assert_eq!(batch_transaction.priority_fee_lamports, Some(10_000));
+            assert_eq!(batch_transaction.estimated_fee_lamports, 2 * LAMPORTS_PER_SIGNATURE + 10_000);
+        }
+    }
+
+    #[test]
+    fn sol_batch_rejects_empty_and_oversized_batches() {
+        let sender = Pubkey::new_unique().to_string();
+        assert!(build_sol_batch(&sol_batch_request(json!({ "from": sender, "recipients": [] }))).is_err());
+        assert!(build_sol_batch(&sol_batch_request(json!({ "from": sender, "recipients": sol_recipients(MAX_BATCH_RECIPIENTS + 1) }))).is_err());
+        assert!(build_sol_batch(&sol_batch_request(json!({ "recipients": sol_recipients(1) }))).is_err());
+    }
+
+    fn token_batch_request(batch_json: serde_json::Value) -> SendTokenBatchRequest {
+        serde_json::from_value(batch_json).unwrap()
+    }
+
+    fn token_recipients(recipient_count: usize) -> Vec<serde_json::Value> {
+        (0..recipient_count)
+            .map(|_| json!({ "destination": Pubkey::new_unique().to_string(), "amount": "1.5" }))
+            .collect()
+    }
+
+    #[test]
+    fn token_batch_packs_ata_creation_with_each_transfer() {
+        let batch_data = build_token_batch(&token_batch_request(json!({
+            "mint": Pubkey::new_unique().to_string(),
+            "owner": Pubkey::new_unique().to_string(),
+            "decimals": 6,
+            "createDestinationAta": true,
+            "recipients": token_recipients(20),
+        })))
+        .unwrap();
+
+        assert!(batch_data.transactions.len() > 1);
+        assert!(batch_data.errors.is_empty());
+        assert_eq!(packed_recipients(&batch_data), (0..20).collect::<Vec<_>>());
+        for batch_transaction in &batch_data.transactions {
+            assert!(batch_transaction.size_bytes <= PACKET_DATA_SIZE);
+            assert_eq!(batch_transaction.instructions.len(), 2 * batch_transaction.recipients.len());
+            assert_eq!(batch_transaction.instructions[0].program_id, spl_associated_token_account::id().to_string());
+            assert_eq!(batch_transaction.instructions[1].program_id, spl_token::id().to_string());
+        }
+    }
+
+    #[test]
+    fn token_batch_reports_invalid_recipients_and_packs_the_rest() {
+        let mut recipients = token_recipients(3);
+        recipients[0] = json!({ "destination": Pubkey::new_unique().to_string(), "amount": "1.0000001" });
+        recipients[2] = json!({ "destination": "not-a-wallet", "amount": 5 });
+
+        let batch_data = build_token_batch(&token_batch_request(json!({
+            "mint": Pubkey::new_unique().to_string(),
+            "owner": Pubkey::new_unique().to_string(),
+            "decimals": 6,
+            "recipients": recipients,
+        })))
+        .unwrap();
+
+        assert_eq!(packed_recipients(&batch_data), vec![1]);
+        let error_indexes: Vec<usize> = batch_data.errors.iter().map(|recipient_error| recipient_error.index).collect();
+        assert_eq!(error_indexes, vec![0, 2]);
+    }
+
+    #[test]
+    fn token_batch_fails_as_a_whole_on_shared_settings() {
+        let mint = Pubkey::new_unique().to_string();
+        let owner = Pubkey::new_unique().to_string();
+        let recipients = token_recipients(2);
+
+        assert!(build_token_batch(&token_batch_request(json!({ "mint": "bad-mint", "owner": owner, "decimals": 6, "recipients": recipients }))).is_err());
+        assert!(build_token_batch(&token_batch_request(json!({ "mint": mint, "owner": owner, "program": "bad-program", "recipients": recipients }))).is_err());
+        assert!(build_token_batch(&token_batch_request(json!({ "mint": mint, "owner": owner, "multisigSigners": ["bad-signer"], "recipients": recipients }))).is_err());
+        assert!(build_token_batch(&token_batch_request(json!({ "mint": mint, "owner": owner, "recipients": [] }))).is_err());
+    }
+
+    #[test]
+    fn token_batch_checks_decimals_before_the_recipients() {
+        let batch_error = build_token_batch(&token_batch_request(json!({
+            "mint": Pubkey::new_unique().to_string(),
+            "owner": Pubkey::new_unique().to_string(),
+            "decimals": 10,
+            "recipients": token_recipients(2),
+        })))
+        .err()
+        .unwrap();
+        assert_eq!(batch_error, "Decimals must be between 0 and 9");
+    }
+
+    #[test]
+    fn token_batch_with_a_multisig_owner_needs_explicit_payers() {
+        let (payer, fee_payer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut multisig_batch_json = json!({
+            "mint": Pubkey::new_unique().to_string(),
+            "owner": Pubkey::new_unique().to_string(),
+            "decimals": 6,
+            "createDestinationAta": true,
+            "multisigSigners": [Pubkey::new_unique().to_string()],
+            "recipients": token_recipients(2),
+        });
+        let batch_error = build_token_batch(&token_batch_request(multisig_batch_json.clone())).err().unwrap();
+        assert!(batch_error.contains("please provide a payer"));
+
+        multisig_batch_json["payer"] = json!(payer.to_string());
+        let batch_error = build_token_batch(&token_batch_request(multisig_batch_json.clone())).err().unwrap();
+        assert!(batch_error.contains("please provide a feePayer"));
+
+        multisig_batch_json["feePayer"] = json!(fee_payer.to_string());
+        let batch_data = build_token_batch(&token_batch_request(multisig_batch_json)).unwrap();
+        assert_eq!(packed_recipients(&batch_data), vec![0, 1]);
+        assert_eq!(batch_data.transactions[0].required_signers[0], fee_payer.to_string());
+        assert!(batch_data.transactions[0].required_signers.contains(&payer.to_string()));
+    }
+}
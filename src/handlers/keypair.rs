@@ -1,15 +1,245 @@
-use axum::response::Json as ResponseJson;
+use axum::{body::Bytes, extract::Json, http::StatusCode, response::Json as ResponseJson};
+use bip39::{Language, Mnemonic, MnemonicType};
+use rayon::prelude::*;
 use solana_sdk::signature::{Keypair, Signer};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
-use crate::models::{ApiResponse, KeypairData};
+use crate::models::{
+    ApiResponse, GenerateKeypairRequest, GrindKeypairData, GrindKeypairRequest, KeypairData, RecoverKeypairRequest,
+    SecretKeyFormat,
+};
+use crate::utils::{encode_secret_key, is_base58_character, keypair_from_mnemonic, result_to_response};
 
-pub async fn generate_keypair() -> ResponseJson<ApiResponse<KeypairData>> {
-    let new_wallet_keypair = Keypair::new();
-    let wallet_public_address = new_wallet_keypair.pubkey().to_string();
-    let wallet_private_key = bs58::encode(&new_wallet_keypair.to_bytes()).into_string();
+const MAX_GRIND_PATTERN_LENGTH: usize = 12;
+const DEFAULT_GRIND_TIMEOUT_SECONDS: u64 = 10;
+const MAX_GRIND_TIMEOUT_SECONDS: u64 = 60;
+const DEFAULT_GRIND_ATTEMPTS: u64 = 10_000_000;
+const MAX_GRIND_ATTEMPTS: u64 = 100_000_000;
 
-    ResponseJson(ApiResponse::success(KeypairData { 
-        pubkey: wallet_public_address, 
-        secret: wallet_private_key 
-    }))
-} 
\ No newline at end of file
+fn keypair_to_response(
+    wallet_keypair: &Keypair,
+    secret_format: Option<SecretKeyFormat>,
+    mnemonic: Option<String>,
+    derivation_path: Option<String>,
+) -> KeypairData {
+    let secret_format = secret_format.unwrap_or_default();
+    KeypairData {
+        pubkey: wallet_keypair.pubkey().to_string(),
+        secret: encode_secret_key(wallet_keypair, secret_format),
+        secret_format,
+        mnemonic,
+        derivation_path,
+    }
+}
+
+pub fn build_generated_keypair(keypair_request: &GenerateKeypairRequest) -> Result<KeypairData, String> {
+    let wants_mnemonic = keypair_request.mnemonic.unwrap_or(keypair_request.words.is_some());
+    if !wants_mnemonic {
+        if keypair_request.passphrase.is_some() || keypair_request.account_index.is_some() {
+            return Err("A passphrase or account index can only be used when generating a mnemonic".to_string());
+        }
+
+        return Ok(keypair_to_response(&Keypair::new(), keypair_request.format, None, None));
+    }
+
+    let mnemonic_type = match keypair_request.words {
+        None | Some(12) => MnemonicType::Words12,
+        Some(24) => MnemonicType::Words24,
+        Some(_) => return Err("A mnemonic must have either 12 or 24 words".to_string()),
+    };
+
+    let generated_mnemonic = Mnemonic::new(mnemonic_type, Language::English);
+    let (derived_keypair, derivation_path) = keypair_from_mnemonic(
+        &generated_mnemonic,
+        keypair_request.passphrase.as_deref().unwrap_or(""),
+        keypair_request.account_index.unwrap_or(0),
+    )?;
+
+    Ok(keypair_to_response(&derived_keypair, keypair_request.format, Some(generated_mnemonic.into_phrase()), Some(derivation_path)))
+}
+
+// A bare POST with an empty body, with or without a JSON content type, keeps generating a plain random keypair
+fn parse_generate_keypair_request(request_body: &[u8]) -> Result<GenerateKeypairRequest, String> {
+    if request_body.trim_ascii().is_empty() {
+        return Ok(GenerateKeypairRequest::default());
+    }
+
+    serde_json::from_slice(request_body).map_err(|parse_error| format!("Invalid keypair request body: {}", parse_error))
+}
+
+pub async fn generate_keypair(request_body: Bytes) -> (StatusCode, ResponseJson<ApiResponse<KeypairData>>) {
+    result_to_response(parse_generate_keypair_request(&request_body).and_then(|keypair_request| build_generated_keypair(&keypair_request)))
+}
+
+pub fn build_recovered_keypair(recover_request: &RecoverKeypairRequest) -> Result<KeypairData, String> {
+    let mnemonic_phrase = match &recover_request.mnemonic {
+        Some(mnemonic_phrase) if !mnemonic_phrase.trim().is_empty() => mnemonic_phrase,
+        _ => return Err("Please provide the mnemonic phrase to recover from".to_string()),
+    };
+
+    let normalized_phrase = mnemonic_phrase.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let recovered_mnemonic = Mnemonic::from_phrase(&normalized_phrase, Language::English)
+        .map_err(|_| "The mnemonic is not a valid BIP39 English phrase; check the words and their order".to_string())?;
+
+    let (derived_keypair, derivation_path) = keypair_from_mnemonic(
+        &recovered_mnemonic,
+        recover_request.passphrase.as_deref().unwrap_or(""),
+        recover_request.account_index.unwrap_or(0),
+    )?;
+
+    Ok(keypair_to_response(&derived_keypair, recover_request.format, None, Some(derivation_path)))
+}
+
+pub async fn recover_keypair(
+    Json(recover_request): Json<RecoverKeypairRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<KeypairData>>) {
+    result_to_response(build_recovered_keypair(&recover_request))
+}
+
+fn parse_vanity_pattern<'a>(pattern: Option<&'a String>, pattern_name: &str) -> Result<&'a str, String> {
+    let pattern = pattern.map(String::as_str).unwrap_or("");
+    match pattern.chars().find(|character| !is_base58_character(*character)) {
+        Some(invalid_character) => Err(format!("The {} contains '{}', which is not a base58 character", pattern_name, invalid_character)),
+        None => Ok(pattern),
+    }
+}
+
+fn vanity_match_length(address: &str, prefix: &str, suffix: &str, case_insensitive: bool) -> usize {
+    let characters_match = |(address_character, pattern_character): &(char, char)| {
+        if case_insensitive {
+            address_character.eq_ignore_ascii_case(pattern_character)
+        } else {
+            address_character == pattern_character
+        }
+    };
+
+    let prefix_length = address.chars().zip(prefix.chars()).take_while(characters_match).count();
+    let suffix_length = address.chars().rev().zip(suffix.chars().rev()).take_while(characters_match).count();
+    prefix_length + suffix_length
+}
+
+// Every worker keeps its own best candidate and the shared counters stop them all on a match, the deadline or the attempt cap
+fn grind_vanity_keypair(
+    prefix: &str,
+    suffix: &str,
+    case_insensitive: bool,
+    deadline: Instant,
+    max_attempts: u64,
+    attempt_counter: &AtomicU64,
+) -> Option<(usize, Keypair)> {
+    let full_match_length = prefix.len() + suffix.len();
+    let match_found = AtomicBool::new(false);
+
+    (0..rayon::current_num_threads())
+        .into_par_iter()
+        .map(|_| {
+            let mut best_candidate: Option<(usize, Keypair)> = None;
+            while !match_found.load(Ordering::Relaxed)
+                && Instant::now() < deadline
+                && attempt_counter.fetch_add(1, Ordering::Relaxed) < max_attempts
+            {
+                let candidate_keypair = Keypair::new();
+                let matched_length = vanity_match_length(&candidate_keypair.pubkey().to_string(), prefix, suffix, case_insensitive);
+                if best_candidate.as_ref().is_none_or(|(best_length, _)| matched_length > *best_length) {
+                    if matched_length == full_match_length {
+                        match_found.store(true, Ordering::Relaxed);
+                    }
+                    best_candidate = Some((matched_length, candidate_keypair));
+                }
+            }
+            best_candidate
+        })
+        .reduce(
+            || None,
+            |left_candidate, right_candidate| match (left_candidate, right_candidate) {
+                (Some(left), Some(right)) => Some(if right.0 > left.0 { right } else { left }),
+                (left, right) => left.or(right),
+            },
+        )
+}
+
+pub fn build_grind_keypair(grind_request: &GrindKeypairRequest) -> Result<GrindKeypairData, String> {
+    let prefix = parse_vanity_pattern(grind_request.prefix.as_ref(), "prefix")?;
+    let suffix = parse_vanity_pattern(grind_request.suffix.as_ref(), "suffix")?;
+    if prefix.is_empty() && suffix.is_empty() {
+        return Err("Please provide a prefix or suffix to search for".to_string());
+    }
+
+    if prefix.len() + suffix.len() > MAX_GRIND_PATTERN_LENGTH {
+        return Err(format!("The prefix and suffix can have at most {} characters combined", MAX_GRIND_PATTERN_LENGTH));
+    }
+
+    let timeout_seconds = match grind_request.timeout_seconds {
+        Some(timeout_seconds) if timeout_seconds == 0 || timeout_seconds > MAX_GRIND_TIMEOUT_SECONDS => {
+            return Err(format!("The timeout must be between 1 and {} seconds", MAX_GRIND_TIMEOUT_SECONDS));
+        }
+        Some(timeout_seconds) => timeout_seconds,
+        None => DEFAULT_GRIND_TIMEOUT_SECONDS,
+    };
+
+    let max_attempts = match grind_request.max_attempts {
+        Some(max_attempts) if max_attempts == 0 || max_attempts > MAX_GRIND_ATTEMPTS => {
+            return Err(format!("The attempt cap must be between 1 and {}", MAX_GRIND_ATTEMPTS));
+        }
+        Some(max_attempts) => max_attempts,
+        None => DEFAULT_GRIND_ATTEMPTS,
+    };
+
+    let search_started = Instant::now();
+    let attempt_counter = AtomicU64::new(0);
+    let (matched_characters, best_keypair) = grind_vanity_keypair(
+        prefix,
+        suffix,
+        grind_request.case_insensitive.unwrap_or(false),
+        search_started + Duration::from_secs(timeout_seconds),
+        max_attempts,
+        &attempt_counter,
+    )
+    .ok_or_else(|| "The search ended before any keypair was generated".to_string())?;
+
+    Ok(GrindKeypairData {
+        pubkey: best_keypair.pubkey().to_string(),
+        secret: bs58::encode(&best_keypair.to_bytes()).into_string(),
+        found: matched_characters == prefix.len() + suffix.len(),
+        matched_characters,
+        attempts: attempt_counter.load(Ordering::Relaxed).min(max_attempts),
+        elapsed_ms: search_started.elapsed().as_millis() as u64,
+    })
+}
+
+pub async fn grind_keypair(
+    Json(grind_request): Json<GrindKeypairRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<GrindKeypairData>>) {
+    // The search pins every core, so it runs off the async workers
+    let grind_result = tokio::task::spawn_blocking(move || build_grind_keypair(&grind_request)).await;
+
+    match grind_result {
+        Ok(Ok(grind_data)) => (StatusCode::OK, ResponseJson(ApiResponse::success(grind_data))),
+        Ok(Err(validation_error)) => (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(validation_error))),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(ApiResponse::error("The keypair search stopped unexpectedly".to_string()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_generate_bodies_fall_back_to_the_default_request() {
+        for request_body in [&b""[..], b"  \n"] {
+            let keypair_request = parse_generate_keypair_request(request_body).unwrap();
+            assert!(keypair_request.mnemonic.is_none() && keypair_request.words.is_none() && keypair_request.format.is_none());
+        }
+    }
+
+    #[test]
+    fn generate_bodies_are_parsed_as_json() {
+        let keypair_request = parse_generate_keypair_request(br#"{"words": 24, "accountIndex": 2}"#).unwrap();
+        assert_eq!(keypair_request.words, Some(24));
+        assert_eq!(keypair_request.account_index, Some(2));
+
+        let parse_error = parse_generate_keypair_request(b"{bad").err().unwrap();
+        assert!(parse_error.starts_with("Invalid keypair request body"));
+    }
+}
@@ -0,0 +1,46 @@
+use axum::{extract::Json, http::StatusCode, response::Json as ResponseJson};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::models::{ApiResponse, InstructionData, InstructionOutput, MemoRequest};
+use crate::utils::{instructions_result_to_response, parse_pubkey};
+
+// Memo v2 rejects data that is not UTF-8; a JSON string is already guaranteed to be valid UTF-8
+pub fn build_memo_instruction(memo_text: &str, signer_public_keys: &[&Pubkey]) -> Result<Instruction, String> {
+    if memo_text.is_empty() {
+        return Err("The memo cannot be empty".to_string());
+    }
+
+    if memo_text.len() > 1000 {
+        return Err("Your memo is too long - please keep it under 1000 characters".to_string());
+    }
+
+    Ok(spl_memo::build_memo(memo_text.as_bytes(), signer_public_keys))
+}
+
+pub fn build_standalone_memo_instruction(memo_request: &MemoRequest) -> Result<Instruction, String> {
+    let memo_text = match &memo_request.memo {
+        Some(memo_text) => memo_text,
+        None => return Err("Please provide the memo text".to_string()),
+    };
+
+    let mut memo_signers: Vec<Pubkey> = Vec::new();
+    for signer_address in memo_request.signers.iter().flatten() {
+        let signer_public_key = parse_pubkey(signer_address)?;
+        if signer_public_key == solana_program::system_program::id() {
+            return Err("The system program cannot sign a memo".to_string());
+        }
+        if memo_signers.contains(&signer_public_key) {
+            return Err(format!("The memo signer {} is listed more than once", signer_public_key));
+        }
+        memo_signers.push(signer_public_key);
+    }
+
+    let memo_signer_refs: Vec<&Pubkey> = memo_signers.iter().collect();
+    build_memo_instruction(memo_text, &memo_signer_refs)
+}
+
+pub async fn create_memo(
+    Json(memo_request): Json<MemoRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_standalone_memo_instruction(&memo_request).map(|instruction| vec![instruction]), &memo_request.compute_budget)
+}
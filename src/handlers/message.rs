@@ -4,7 +4,7 @@ use solana_sdk::signature::{Signature, Signer};
 use crate::models::{
     ApiResponse, SignMessageData, SignMessageRequest, VerifyMessageData, VerifyMessageRequest,
 };
-use crate::utils::{keypair_from_base58, parse_pubkey};
+use crate::utils::{keypair_from_secret, parse_pubkey};
 
 pub async fn sign_message(
     Json(message_request): Json<SignMessageRequest>,
@@ -17,12 +17,13 @@ pub async fn sign_message(
     };
     
     let user_private_key = match &message_request.secret {
-        Some(private_key_string) if !private_key_string.is_empty() => private_key_string,
-        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("A valid private key is required for message signing".to_string()))),
+        Some(serde_json::Value::String(private_key_string)) if private_key_string.is_empty() => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("A valid private key is required for message signing".to_string()))),
+        Some(private_key) => private_key,
+        None => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("A valid private key is required for message signing".to_string()))),
     };
 
-    let wallet_keypair = match keypair_from_base58(user_private_key) {
-        Ok(valid_keypair) => valid_keypair,
+    let (wallet_keypair, secret_format) = match keypair_from_secret(user_private_key) {
+        Ok(parsed_secret) => parsed_secret,
         Err(error_message) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(error_message))),
     };
 
@@ -33,6 +34,7 @@ pub async fn sign_message(
         signature: bs58::encode(&digital_signature.as_ref()).into_string(),
         pubkey: wallet_keypair.pubkey().to_string(),
         message: user_message.clone(),
+        secret_format,
     })))
 }
 
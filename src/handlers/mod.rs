@@ -1,9 +1,29 @@
+pub mod address;
+pub mod batch;
 pub mod keypair;
+pub mod memo;
 pub mod message;
+pub mod nonce;
+pub mod stake;
+pub mod system;
 pub mod token;
+pub mod token_account;
+pub mod token_authority;
+pub mod transaction;
 pub mod transfer;
+pub mod wrapped_sol;
 
+pub use address::*;
+pub use batch::*;
 pub use keypair::*;
+pub use memo::*;
 pub use message::*;
+pub use nonce::*;
+pub use stake::*;
+pub use system::*;
 pub use token::*;
-pub use transfer::*; 
\ No newline at end of file
+pub use token_account::*;
+pub use token_authority::*;
+pub use transaction::*;
+pub use transfer::*;
+pub use wrapped_sol::*; 
\ No newline at end of file
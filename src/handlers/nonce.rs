@@ -0,0 +1,158 @@
+use axum::{extract::Json, http::StatusCode, response::Json as ResponseJson};
+use solana_program::{instruction::Instruction, nonce, pubkey::Pubkey, system_instruction};
+
+use crate::handlers::AccountCreationPlan;
+use crate::models::{
+    AdvanceNonceAccountRequest, ApiResponse, AuthorizeNonceAccountRequest, CreateNonceAccountRequest,
+    InstructionBundleData, InstructionData, InstructionOutput, WithdrawNonceAccountRequest,
+};
+use crate::utils::{
+    instruction_to_response, instructions_result_to_response, instructions_to_bundle, parse_optional_pubkey, parse_pubkey,
+    rent_from_schedule, result_to_response,
+};
+
+pub fn build_create_nonce_account_plan(nonce_request: &CreateNonceAccountRequest) -> Result<AccountCreationPlan, String> {
+    let nonce_account_address = match &nonce_request.nonce_account {
+        Some(account_address) if !account_address.is_empty() => account_address,
+        _ => return Err("Please provide the address for the new nonce account".to_string()),
+    };
+
+    let payer_address = match &nonce_request.payer {
+        Some(payer_address) if !payer_address.is_empty() => payer_address,
+        _ => return Err("A payer address is required to fund the nonce account".to_string()),
+    };
+
+    let nonce_account_public_key = parse_pubkey(nonce_account_address)?;
+    let payer_public_key = parse_pubkey(payer_address)?;
+    let nonce_authority_public_key = parse_optional_pubkey(nonce_request.authority.as_deref())?.unwrap_or(payer_public_key);
+
+    if nonce_account_public_key == solana_program::system_program::id()
+        || payer_public_key == solana_program::system_program::id()
+        || nonce_authority_public_key == solana_program::system_program::id()
+    {
+        return Err("The system program cannot be used as the nonce account, its payer or its authority".to_string());
+    }
+
+    if nonce_account_public_key == payer_public_key {
+        return Err("The nonce account must be a new address, not the payer".to_string());
+    }
+
+    let nonce_account_space = nonce::State::size();
+    let rent_exempt_lamports = rent_from_schedule(nonce_request.rent_schedule.as_ref())?.minimum_balance(nonce_account_space);
+
+    let funding_lamports = match nonce_request.lamports {
+        Some(lamports) if lamports < rent_exempt_lamports => {
+            return Err(format!("A nonce account needs at least {} lamports to be rent exempt", rent_exempt_lamports));
+        }
+        Some(lamports) => lamports,
+        None => rent_exempt_lamports,
+    };
+
+    Ok(AccountCreationPlan {
+        instructions: system_instruction::create_nonce_account(&payer_public_key, &nonce_account_public_key, &nonce_authority_public_key, funding_lamports),
+        account_space: nonce_account_space,
+        rent_exempt_lamports,
+    })
+}
+
+pub async fn create_nonce_account(
+    Json(nonce_request): Json<CreateNonceAccountRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionBundleData>>) {
+    let nonce_bundle = build_create_nonce_account_plan(&nonce_request).and_then(|nonce_plan| {
+        let mut nonce_bundle = instructions_to_bundle(nonce_plan.instructions, &nonce_request.compute_budget, instruction_to_response)?;
+        nonce_bundle.account_space = Some(nonce_plan.account_space);
+        nonce_bundle.rent_exempt_lamports = Some(nonce_plan.rent_exempt_lamports);
+        Ok(nonce_bundle)
+    });
+
+    result_to_response(nonce_bundle)
+}
+
+pub fn parse_nonce_account_and_authority(nonce_account: Option<&str>, authority: Option<&str>) -> Result<(Pubkey, Pubkey), String> {
+    let nonce_account_address = match nonce_account {
+        Some(account_address) if !account_address.is_empty() => account_address,
+        _ => return Err("Please provide the nonce account address".to_string()),
+    };
+
+    let nonce_authority_address = match authority {
+        Some(authority_address) if !authority_address.is_empty() => authority_address,
+        _ => return Err("The nonce authority address is required to sign this operation".to_string()),
+    };
+
+    let nonce_account_public_key = parse_pubkey(nonce_account_address)?;
+    let nonce_authority_public_key = parse_pubkey(nonce_authority_address)?;
+
+    if nonce_account_public_key == solana_program::system_program::id() || nonce_authority_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot be used as the nonce account or its authority".to_string());
+    }
+
+    Ok((nonce_account_public_key, nonce_authority_public_key))
+}
+
+pub fn build_advance_nonce_account_instruction(advance_request: &AdvanceNonceAccountRequest) -> Result<Instruction, String> {
+    let (nonce_account_public_key, nonce_authority_public_key) =
+        parse_nonce_account_and_authority(advance_request.nonce_account.as_deref(), advance_request.authority.as_deref())?;
+
+    Ok(system_instruction::advance_nonce_account(&nonce_account_public_key, &nonce_authority_public_key))
+}
+
+pub fn build_withdraw_nonce_account_instruction(withdraw_request: &WithdrawNonceAccountRequest) -> Result<Instruction, String> {
+    let (nonce_account_public_key, nonce_authority_public_key) =
+        parse_nonce_account_and_authority(withdraw_request.nonce_account.as_deref(), withdraw_request.authority.as_deref())?;
+
+    let lamports_to_withdraw = match withdraw_request.lamports {
+        Some(0) => return Err("Amount must be greater than 0".to_string()),
+        Some(amount) => amount,
+        None => return Err("Please specify how many lamports you want to withdraw".to_string()),
+    };
+
+    let withdrawal_destination = parse_optional_pubkey(withdraw_request.destination.as_deref())?.unwrap_or(nonce_authority_public_key);
+    if withdrawal_destination == solana_program::system_program::id() {
+        return Err("Lamports cannot be withdrawn to the system program".to_string());
+    }
+
+    if withdrawal_destination == nonce_account_public_key {
+        return Err("Cannot withdraw to the nonce account itself".to_string());
+    }
+
+    Ok(system_instruction::withdraw_nonce_account(&nonce_account_public_key, &nonce_authority_public_key, &withdrawal_destination, lamports_to_withdraw))
+}
+
+pub fn build_authorize_nonce_account_instruction(authorize_request: &AuthorizeNonceAccountRequest) -> Result<Instruction, String> {
+    let (nonce_account_public_key, nonce_authority_public_key) =
+        parse_nonce_account_and_authority(authorize_request.nonce_account.as_deref(), authorize_request.authority.as_deref())?;
+
+    let new_authority_address = match &authorize_request.new_authority {
+        Some(authority_address) if !authority_address.is_empty() => authority_address,
+        _ => return Err("Please provide the new nonce authority".to_string()),
+    };
+
+    let new_authority_public_key = parse_pubkey(new_authority_address)?;
+    if new_authority_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot be used as a nonce authority".to_string());
+    }
+
+    if new_authority_public_key == nonce_authority_public_key {
+        return Err("The new authority is the same as the current authority".to_string());
+    }
+
+    Ok(system_instruction::authorize_nonce_account(&nonce_account_public_key, &nonce_authority_public_key, &new_authority_public_key))
+}
+
+pub async fn advance_nonce_account(
+    Json(advance_request): Json<AdvanceNonceAccountRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_advance_nonce_account_instruction(&advance_request).map(|instruction| vec![instruction]), &advance_request.compute_budget)
+}
+
+pub async fn withdraw_nonce_account(
+    Json(withdraw_request): Json<WithdrawNonceAccountRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_withdraw_nonce_account_instruction(&withdraw_request).map(|instruction| vec![instruction]), &withdraw_request.compute_budget)
+}
+
+pub async fn authorize_nonce_account(
+    Json(authorize_request): Json<AuthorizeNonceAccountRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_authorize_nonce_account_instruction(&authorize_request).map(|instruction| vec![instruction]), &authorize_request.compute_budget)
+}
@@ -0,0 +1,282 @@
+use axum::{extract::Json, http::StatusCode, response::Json as ResponseJson};
+use solana_program::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    stake::{
+        instruction as stake_instruction,
+        state::{Authorized, Lockup, StakeStateV2},
+    },
+    system_instruction,
+};
+
+use crate::handlers::AccountCreationPlan;
+use crate::models::{
+    ApiResponse, ComputeBudgetSpec, CreateStakeAccountRequest, DeactivateStakeRequest, DelegateStakeRequest,
+    InstructionBundleData, InstructionData, InstructionOutput, MergeStakeRequest, SplitStakeRequest,
+    WithdrawStakeRequest,
+};
+use crate::utils::{
+    instruction_to_response, instructions_result_to_response, instructions_to_bundle, parse_optional_pubkey, parse_pubkey,
+    rent_from_schedule, result_to_response,
+};
+
+fn stake_plan_to_response(
+    plan_result: Result<AccountCreationPlan, String>,
+    compute_budget: &ComputeBudgetSpec,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionBundleData>>) {
+    let stake_bundle = plan_result.and_then(|stake_plan| {
+        let mut stake_bundle = instructions_to_bundle(stake_plan.instructions, compute_budget, instruction_to_response)?;
+        stake_bundle.account_space = Some(stake_plan.account_space);
+        stake_bundle.rent_exempt_lamports = Some(stake_plan.rent_exempt_lamports);
+        Ok(stake_bundle)
+    });
+
+    result_to_response(stake_bundle)
+}
+
+fn parse_stake_account_and_authority(stake_account: Option<&str>, authority: Option<&str>, authority_name: &str) -> Result<(Pubkey, Pubkey), String> {
+    let stake_account_address = match stake_account {
+        Some(account_address) if !account_address.is_empty() => account_address,
+        _ => return Err("Please provide the stake account address".to_string()),
+    };
+
+    let authority_address = match authority {
+        Some(authority_address) if !authority_address.is_empty() => authority_address,
+        _ => return Err(format!("The {} address is required to sign this operation", authority_name)),
+    };
+
+    let stake_account_public_key = parse_pubkey(stake_account_address)?;
+    let authority_public_key = parse_pubkey(authority_address)?;
+
+    if stake_account_public_key == solana_program::system_program::id() || authority_public_key == solana_program::system_program::id() {
+        return Err(format!("The system program cannot be used as the stake account or its {}", authority_name));
+    }
+
+    Ok((stake_account_public_key, authority_public_key))
+}
+
+pub fn build_create_stake_account_plan(stake_request: &CreateStakeAccountRequest) -> Result<AccountCreationPlan, String> {
+    let stake_account_address = match &stake_request.stake_account {
+        Some(account_address) if !account_address.is_empty() => account_address,
+        _ => return Err("Please provide the address for the new stake account".to_string()),
+    };
+
+    let payer_address = match &stake_request.payer {
+        Some(payer_address) if !payer_address.is_empty() => payer_address,
+        _ => return Err("A payer address is required to fund the stake account".to_string()),
+    };
+
+    let lamports_to_stake = match stake_request.lamports {
+        Some(0) | None => return Err("Please specify how many lamports to put in the stake account".to_string()),
+        Some(amount) => amount,
+    };
+
+    let stake_account_public_key = parse_pubkey(stake_account_address)?;
+    let payer_public_key = parse_pubkey(payer_address)?;
+    let staker_public_key = parse_optional_pubkey(stake_request.staker.as_deref())?.unwrap_or(payer_public_key);
+    let withdrawer_public_key = parse_optional_pubkey(stake_request.withdrawer.as_deref())?.unwrap_or(staker_public_key);
+    let vote_account_public_key = parse_optional_pubkey(stake_request.vote_account.as_deref())?;
+
+    if [stake_account_public_key, payer_public_key, staker_public_key, withdrawer_public_key].contains(&solana_program::system_program::id()) {
+        return Err("The system program cannot be used as the stake account, its payer or its authorities".to_string());
+    }
+
+    if stake_account_public_key == payer_public_key {
+        return Err("The stake account must be a new address, not the payer".to_string());
+    }
+
+    let stake_lockup = match &stake_request.lockup {
+        Some(lockup_spec) => Lockup {
+            unix_timestamp: lockup_spec.unix_timestamp.unwrap_or(0),
+            epoch: lockup_spec.epoch.unwrap_or(0),
+            custodian: parse_optional_pubkey(lockup_spec.custodian.as_deref())?.unwrap_or_default(),
+        },
+        None => Lockup::default(),
+    };
+
+    let stake_account_space = StakeStateV2::size_of();
+    let rent_exempt_lamports = rent_from_schedule(stake_request.rent_schedule.as_ref())?.minimum_balance(stake_account_space);
+    if lamports_to_stake < rent_exempt_lamports {
+        return Err(format!("A stake account needs at least {} lamports to be rent exempt", rent_exempt_lamports));
+    }
+
+    let stake_authorities = Authorized {
+        staker: staker_public_key,
+        withdrawer: withdrawer_public_key,
+    };
+
+    let stake_instructions = match vote_account_public_key {
+        Some(_) if lamports_to_stake == rent_exempt_lamports => {
+            return Err(format!("Delegating requires lamports above the {} lamport rent-exempt reserve", rent_exempt_lamports));
+        }
+        Some(vote_account) => stake_instruction::create_account_and_delegate_stake(
+            &payer_public_key,
+            &stake_account_public_key,
+            &vote_account,
+            &stake_authorities,
+            &stake_lockup,
+            lamports_to_stake,
+        ),
+        None => stake_instruction::create_account(&payer_public_key, &stake_account_public_key, &stake_authorities, &stake_lockup, lamports_to_stake),
+    };
+
+    Ok(AccountCreationPlan {
+        instructions: stake_instructions,
+        account_space: stake_account_space,
+        rent_exempt_lamports,
+    })
+}
+
+pub fn build_delegate_stake_instruction(delegate_request: &DelegateStakeRequest) -> Result<Instruction, String> {
+    let (stake_account_public_key, staker_public_key) =
+        parse_stake_account_and_authority(delegate_request.stake_account.as_deref(), delegate_request.staker.as_deref(), "staker")?;
+
+    let vote_account_address = match &delegate_request.vote_account {
+        Some(vote_address) if !vote_address.is_empty() => vote_address,
+        _ => return Err("Please provide the vote account of the validator to delegate to".to_string()),
+    };
+
+    let vote_account_public_key = parse_pubkey(vote_account_address)?;
+    if vote_account_public_key == stake_account_public_key {
+        return Err("A stake account cannot be delegated to itself".to_string());
+    }
+
+    Ok(stake_instruction::delegate_stake(&stake_account_public_key, &staker_public_key, &vote_account_public_key))
+}
+
+pub fn build_deactivate_stake_instruction(deactivate_request: &DeactivateStakeRequest) -> Result<Instruction, String> {
+    let (stake_account_public_key, staker_public_key) =
+        parse_stake_account_and_authority(deactivate_request.stake_account.as_deref(), deactivate_request.staker.as_deref(), "staker")?;
+
+    Ok(stake_instruction::deactivate_stake(&stake_account_public_key, &staker_public_key))
+}
+
+pub fn build_withdraw_stake_instruction(withdraw_request: &WithdrawStakeRequest) -> Result<Instruction, String> {
+    let (stake_account_public_key, withdrawer_public_key) =
+        parse_stake_account_and_authority(withdraw_request.stake_account.as_deref(), withdraw_request.withdrawer.as_deref(), "withdrawer")?;
+
+    let lamports_to_withdraw = match withdraw_request.lamports {
+        Some(0) => return Err("Amount must be greater than 0".to_string()),
+        Some(amount) => amount,
+        None => return Err("Please specify how many lamports you want to withdraw".to_string()),
+    };
+
+    let withdrawal_destination = parse_optional_pubkey(withdraw_request.destination.as_deref())?.unwrap_or(withdrawer_public_key);
+    let lockup_custodian = parse_optional_pubkey(withdraw_request.custodian.as_deref())?;
+
+    if withdrawal_destination == solana_program::system_program::id() {
+        return Err("Lamports cannot be withdrawn to the system program".to_string());
+    }
+
+    if withdrawal_destination == stake_account_public_key {
+        return Err("Cannot withdraw to the stake account itself".to_string());
+    }
+
+    Ok(stake_instruction::withdraw(
+        &stake_account_public_key,
+        &withdrawer_public_key,
+        &withdrawal_destination,
+        lamports_to_withdraw,
+        lockup_custodian.as_ref(),
+    ))
+}
+
+pub fn build_split_stake_plan(split_request: &SplitStakeRequest) -> Result<AccountCreationPlan, String> {
+    let (stake_account_public_key, staker_public_key) =
+        parse_stake_account_and_authority(split_request.stake_account.as_deref(), split_request.staker.as_deref(), "staker")?;
+
+    let split_account_address = match &split_request.split_stake_account {
+        Some(account_address) if !account_address.is_empty() => account_address,
+        _ => return Err("Please provide the address for the new split stake account".to_string()),
+    };
+
+    let lamports_to_split = match split_request.lamports {
+        Some(0) => return Err("Amount must be greater than 0".to_string()),
+        Some(amount) => amount,
+        None => return Err("Please specify how many lamports to move into the split stake account".to_string()),
+    };
+
+    let split_account_public_key = parse_pubkey(split_account_address)?;
+    let rent_payer_public_key = parse_optional_pubkey(split_request.payer.as_deref())?;
+
+    if split_account_public_key == solana_program::system_program::id() || rent_payer_public_key == Some(solana_program::system_program::id()) {
+        return Err("The system program cannot be used as the split stake account or its payer".to_string());
+    }
+
+    if split_account_public_key == stake_account_public_key {
+        return Err("The split stake account must be different from the source stake account".to_string());
+    }
+
+    let stake_account_space = StakeStateV2::size_of();
+    let rent_exempt_lamports = rent_from_schedule(split_request.rent_schedule.as_ref())?.minimum_balance(stake_account_space);
+
+    // The stake program requires the split destination to already hold its rent-exempt reserve
+    let mut split_instructions = Vec::new();
+    if let Some(rent_payer) = rent_payer_public_key {
+        split_instructions.push(system_instruction::transfer(&rent_payer, &split_account_public_key, rent_exempt_lamports));
+    }
+    split_instructions.extend(stake_instruction::split(&stake_account_public_key, &staker_public_key, lamports_to_split, &split_account_public_key));
+
+    Ok(AccountCreationPlan {
+        instructions: split_instructions,
+        account_space: stake_account_space,
+        rent_exempt_lamports,
+    })
+}
+
+pub fn build_merge_stake_instructions(merge_request: &MergeStakeRequest) -> Result<Vec<Instruction>, String> {
+    let (destination_stake_public_key, staker_public_key) =
+        parse_stake_account_and_authority(merge_request.destination_stake_account.as_deref(), merge_request.staker.as_deref(), "staker")?;
+
+    let source_stake_address = match &merge_request.source_stake_account {
+        Some(account_address) if !account_address.is_empty() => account_address,
+        _ => return Err("Please provide the source stake account to merge".to_string()),
+    };
+
+    let source_stake_public_key = parse_pubkey(source_stake_address)?;
+    if source_stake_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot be used as a stake account".to_string());
+    }
+
+    if source_stake_public_key == destination_stake_public_key {
+        return Err("Cannot merge a stake account into itself".to_string());
+    }
+
+    Ok(stake_instruction::merge(&destination_stake_public_key, &source_stake_public_key, &staker_public_key))
+}
+
+pub async fn create_stake_account(
+    Json(stake_request): Json<CreateStakeAccountRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionBundleData>>) {
+    stake_plan_to_response(build_create_stake_account_plan(&stake_request), &stake_request.compute_budget)
+}
+
+pub async fn delegate_stake(
+    Json(delegate_request): Json<DelegateStakeRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_delegate_stake_instruction(&delegate_request).map(|instruction| vec![instruction]), &delegate_request.compute_budget)
+}
+
+pub async fn deactivate_stake(
+    Json(deactivate_request): Json<DeactivateStakeRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_deactivate_stake_instruction(&deactivate_request).map(|instruction| vec![instruction]), &deactivate_request.compute_budget)
+}
+
+pub async fn withdraw_stake(
+    Json(withdraw_request): Json<WithdrawStakeRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_withdraw_stake_instruction(&withdraw_request).map(|instruction| vec![instruction]), &withdraw_request.compute_budget)
+}
+
+pub async fn split_stake(
+    Json(split_request): Json<SplitStakeRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionBundleData>>) {
+    stake_plan_to_response(build_split_stake_plan(&split_request), &split_request.compute_budget)
+}
+
+pub async fn merge_stake(
+    Json(merge_request): Json<MergeStakeRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_merge_stake_instructions(&merge_request), &merge_request.compute_budget)
+}
@@ -0,0 +1,231 @@
+use axum::{extract::Json, http::StatusCode, response::Json as ResponseJson};
+use solana_program::{
+    instruction::Instruction,
+    pubkey::{Pubkey, PubkeyError},
+    system_instruction::{self, MAX_PERMITTED_DATA_LENGTH},
+};
+
+use crate::models::{
+    AllocateSystemAccountRequest, ApiResponse, AssignSystemAccountRequest, ComputeBudgetSpec,
+    CreateSystemAccountRequest, RentScheduleSpec, SystemAccountData,
+};
+use crate::utils::{instruction_to_response, instructions_to_output, parse_pubkey, rent_from_schedule, result_to_response};
+
+pub struct SystemAccountPlan {
+    pub instruction: Instruction,
+    pub address: Pubkey,
+    pub account_space: Option<u64>,
+    pub rent_exempt_lamports: Option<u64>,
+}
+
+fn system_plan_to_response(
+    plan_result: Result<SystemAccountPlan, String>,
+    compute_budget: &ComputeBudgetSpec,
+) -> (StatusCode, ResponseJson<ApiResponse<SystemAccountData>>) {
+    let system_account_data = plan_result.and_then(|system_plan| {
+        Ok(SystemAccountData {
+            instruction: instructions_to_output(vec![system_plan.instruction], compute_budget, instruction_to_response, instruction_to_response)?,
+            address: system_plan.address.to_string(),
+            account_space: system_plan.account_space,
+            rent_exempt_lamports: system_plan.rent_exempt_lamports,
+        })
+    });
+
+    result_to_response(system_account_data)
+}
+
+fn parse_program_owner(owner_address: Option<&String>) -> Result<Pubkey, String> {
+    match owner_address {
+        Some(owner_address) if !owner_address.is_empty() => parse_pubkey(owner_address),
+        _ => Err("Please provide the program that will own the account".to_string()),
+    }
+}
+
+fn parse_account_space(space: Option<u64>) -> Result<u64, String> {
+    match space {
+        Some(space) if space > MAX_PERMITTED_DATA_LENGTH => Err(format!("Accounts can hold at most {} bytes of data", MAX_PERMITTED_DATA_LENGTH)),
+        Some(space) => Ok(space),
+        None => Err("Please specify how many bytes of space the account needs".to_string()),
+    }
+}
+
+fn rent_for_space(rent_schedule: Option<&RentScheduleSpec>, space: u64) -> Result<u64, String> {
+    Ok(rent_from_schedule(rent_schedule)?.minimum_balance(space as usize))
+}
+
+fn derive_seeded_address<'a>(base: &Pubkey, seed: Option<&'a String>, owner: &Pubkey) -> Result<(Pubkey, &'a str), String> {
+    let seed = match seed {
+        Some(seed) if !seed.is_empty() => seed,
+        _ => return Err("Please provide the seed used to derive the account address".to_string()),
+    };
+
+    match Pubkey::create_with_seed(base, seed, owner) {
+        Ok(seeded_address) => Ok((seeded_address, seed)),
+        Err(PubkeyError::MaxSeedLengthExceeded) => Err("The seed can be at most 32 bytes long".to_string()),
+        Err(PubkeyError::IllegalOwner) => Err("The owner cannot be used to derive a seeded address".to_string()),
+        Err(_) => Err("Unable to derive an address from the provided base, seed and owner".to_string()),
+    }
+}
+
+fn parse_seed_base(base_address: Option<&String>) -> Result<Pubkey, String> {
+    let base_public_key = match base_address {
+        Some(base_address) if !base_address.is_empty() => parse_pubkey(base_address)?,
+        _ => return Err("Please provide the base address that signs for the seeded account".to_string()),
+    };
+
+    if base_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot be used as a seed base".to_string());
+    }
+
+    Ok(base_public_key)
+}
+
+pub fn build_create_system_account_plan(account_request: &CreateSystemAccountRequest, with_seed: bool) -> Result<SystemAccountPlan, String> {
+    let payer_address = match &account_request.payer {
+        Some(payer_address) if !payer_address.is_empty() => payer_address,
+        _ => return Err("A payer address is required to fund the new account".to_string()),
+    };
+
+    let account_space = parse_account_space(account_request.space)?;
+    let program_owner = parse_program_owner(account_request.owner.as_ref())?;
+    let payer_public_key = parse_pubkey(payer_address)?;
+    if payer_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot fund a new account".to_string());
+    }
+
+    let rent_exempt_lamports = rent_for_space(account_request.rent_schedule.as_ref(), account_space)?;
+    let funding_lamports = match account_request.lamports {
+        Some(lamports) if lamports < rent_exempt_lamports => {
+            return Err(format!("An account with {} bytes needs at least {} lamports to be rent exempt", account_space, rent_exempt_lamports));
+        }
+        Some(lamports) => lamports,
+        None => rent_exempt_lamports,
+    };
+
+    let (new_account_public_key, create_instruction) = if with_seed {
+        let base_public_key = match &account_request.base {
+            Some(base_address) if !base_address.is_empty() => parse_seed_base(Some(base_address))?,
+            _ => payer_public_key,
+        };
+        let (seeded_address, seed) = derive_seeded_address(&base_public_key, account_request.seed.as_ref(), &program_owner)?;
+        (
+            seeded_address,
+            system_instruction::create_account_with_seed(&payer_public_key, &seeded_address, &base_public_key, seed, funding_lamports, account_space, &program_owner),
+        )
+    } else {
+        let new_account_public_key = match &account_request.new_account {
+            Some(account_address) if !account_address.is_empty() => parse_pubkey(account_address)?,
+            _ => return Err("Please provide the address for the new account".to_string()),
+        };
+        (
+            new_account_public_key,
+            system_instruction::create_account(&payer_public_key, &new_account_public_key, funding_lamports, account_space, &program_owner),
+        )
+    };
+
+    if new_account_public_key == payer_public_key || new_account_public_key == solana_program::system_program::id() {
+        return Err("The new account must be a fresh address, not the payer or the system program".to_string());
+    }
+
+    Ok(SystemAccountPlan {
+        instruction: create_instruction,
+        address: new_account_public_key,
+        account_space: Some(account_space),
+        rent_exempt_lamports: Some(rent_exempt_lamports),
+    })
+}
+
+pub fn build_allocate_system_account_plan(allocate_request: &AllocateSystemAccountRequest, with_seed: bool) -> Result<SystemAccountPlan, String> {
+    let account_space = match parse_account_space(allocate_request.space)? {
+        0 => return Err("Please specify a space greater than 0 bytes to allocate".to_string()),
+        space => space,
+    };
+    let rent_exempt_lamports = rent_for_space(allocate_request.rent_schedule.as_ref(), account_space)?;
+
+    let (account_public_key, allocate_instruction) = if with_seed {
+        let base_public_key = parse_seed_base(allocate_request.base.as_ref())?;
+        let program_owner = parse_program_owner(allocate_request.owner.as_ref())?;
+        let (seeded_address, seed) = derive_seeded_address(&base_public_key, allocate_request.seed.as_ref(), &program_owner)?;
+        (seeded_address, system_instruction::allocate_with_seed(&seeded_address, &base_public_key, seed, account_space, &program_owner))
+    } else {
+        let account_public_key = match &allocate_request.account {
+            Some(account_address) if !account_address.is_empty() => parse_pubkey(account_address)?,
+            _ => return Err("Please provide the account to allocate space for".to_string()),
+        };
+        (account_public_key, system_instruction::allocate(&account_public_key, account_space))
+    };
+
+    if account_public_key == solana_program::system_program::id() {
+        return Err("Space cannot be allocated for the system program".to_string());
+    }
+
+    Ok(SystemAccountPlan {
+        instruction: allocate_instruction,
+        address: account_public_key,
+        account_space: Some(account_space),
+        rent_exempt_lamports: Some(rent_exempt_lamports),
+    })
+}
+
+pub fn build_assign_system_account_plan(assign_request: &AssignSystemAccountRequest, with_seed: bool) -> Result<SystemAccountPlan, String> {
+    let program_owner = parse_program_owner(assign_request.owner.as_ref())?;
+
+    let (account_public_key, assign_instruction) = if with_seed {
+        let base_public_key = parse_seed_base(assign_request.base.as_ref())?;
+        let (seeded_address, seed) = derive_seeded_address(&base_public_key, assign_request.seed.as_ref(), &program_owner)?;
+        (seeded_address, system_instruction::assign_with_seed(&seeded_address, &base_public_key, seed, &program_owner))
+    } else {
+        let account_public_key = match &assign_request.account {
+            Some(account_address) if !account_address.is_empty() => parse_pubkey(account_address)?,
+            _ => return Err("Please provide the account to assign to a new owner".to_string()),
+        };
+        (account_public_key, system_instruction::assign(&account_public_key, &program_owner))
+    };
+
+    if account_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot be assigned to a new owner".to_string());
+    }
+
+    Ok(SystemAccountPlan {
+        instruction: assign_instruction,
+        address: account_public_key,
+        account_space: None,
+        rent_exempt_lamports: None,
+    })
+}
+
+pub async fn create_system_account(
+    Json(account_request): Json<CreateSystemAccountRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<SystemAccountData>>) {
+    system_plan_to_response(build_create_system_account_plan(&account_request, false), &account_request.compute_budget)
+}
+
+pub async fn create_system_account_with_seed(
+    Json(account_request): Json<CreateSystemAccountRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<SystemAccountData>>) {
+    system_plan_to_response(build_create_system_account_plan(&account_request, true), &account_request.compute_budget)
+}
+
+pub async fn allocate_system_account(
+    Json(allocate_request): Json<AllocateSystemAccountRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<SystemAccountData>>) {
+    system_plan_to_response(build_allocate_system_account_plan(&allocate_request, false), &allocate_request.compute_budget)
+}
+
+pub async fn allocate_system_account_with_seed(
+    Json(allocate_request): Json<AllocateSystemAccountRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<SystemAccountData>>) {
+    system_plan_to_response(build_allocate_system_account_plan(&allocate_request, true), &allocate_request.compute_budget)
+}
+
+pub async fn assign_system_account(
+    Json(assign_request): Json<AssignSystemAccountRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<SystemAccountData>>) {
+    system_plan_to_response(build_assign_system_account_plan(&assign_request, false), &assign_request.compute_budget)
+}
+
+pub async fn assign_system_account_with_seed(
+    Json(assign_request): Json<AssignSystemAccountRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<SystemAccountData>>) {
+    system_plan_to_response(build_assign_system_account_plan(&assign_request, true), &assign_request.compute_budget)
+}
@@ -1,122 +1,470 @@
 use axum::{extract::Json, http::StatusCode, response::Json as ResponseJson};
-use spl_token::instruction as token_instruction;
+use solana_program::{instruction::Instruction, pubkey::Pubkey, system_instruction};
+use spl_token_2022::{
+    extension::{
+        ExtensionType, default_account_state, interest_bearing_mint, metadata_pointer, transfer_fee,
+    },
+    instruction as token_instruction,
+    state::{AccountState, Mint},
+};
+use spl_token_metadata_interface::state::TokenMetadata;
 
-use crate::models::{ApiResponse, CreateTokenRequest, InstructionData, MintTokenRequest};
-use crate::utils::{instruction_to_response, parse_pubkey};
+use crate::models::{
+    ApiResponse, CreateTokenRequest, InstructionData, InstructionOutput, MintExtensionSpec, MintTokenRequest,
+};
+use crate::utils::{
+    instruction_to_response, instructions_result_to_response, instructions_to_output, parse_multisig_signers, parse_optional_pubkey,
+    parse_pubkey, parse_token_decimals, parse_token_program, rent_from_schedule, token_amount_to_base_units,
+};
 
-pub async fn create_token(
-    Json(token_creation_request): Json<CreateTokenRequest>,
-) -> (StatusCode, ResponseJson<ApiResponse<InstructionData>>) {
+pub struct AccountCreationPlan {
+    pub instructions: Vec<Instruction>,
+    pub account_space: usize,
+    pub rent_exempt_lamports: u64,
+}
+
+struct MintExtensionInstructions {
+    before_initialize: Vec<Instruction>,
+    after_initialize: Vec<Instruction>,
+    extension_types: Vec<ExtensionType>,
+    metadata_space: usize,
+}
+
+fn build_mint_extension_instructions(
+    token_program_id: &Pubkey,
+    token_mint_public_key: &Pubkey,
+    mint_authority_public_key: &Pubkey,
+    extension_specs: &[MintExtensionSpec],
+) -> Result<MintExtensionInstructions, String> {
+    let mut before_initialize = Vec::new();
+    let mut after_initialize = Vec::new();
+    let mut extension_types = Vec::new();
+    let mut metadata_space = 0;
+    let mut metadata_pointer_address = None;
+
+    for extension_spec in extension_specs {
+        let extension_type = match extension_spec {
+            MintExtensionSpec::TransferFeeConfig { .. } => ExtensionType::TransferFeeConfig,
+            MintExtensionSpec::MetadataPointer { .. } => ExtensionType::MetadataPointer,
+            MintExtensionSpec::TokenMetadata { .. } => ExtensionType::TokenMetadata,
+            MintExtensionSpec::InterestBearingConfig { .. } => ExtensionType::InterestBearingConfig,
+            MintExtensionSpec::NonTransferable => ExtensionType::NonTransferable,
+            MintExtensionSpec::PermanentDelegate { .. } => ExtensionType::PermanentDelegate,
+            MintExtensionSpec::MintCloseAuthority { .. } => ExtensionType::MintCloseAuthority,
+            MintExtensionSpec::DefaultAccountState { .. } => ExtensionType::DefaultAccountState,
+        };
+        if extension_types.contains(&extension_type) {
+            return Err(format!("The {:?} extension was specified more than once", extension_type));
+        }
+        extension_types.push(extension_type);
+
+        let extension_instruction = match extension_spec {
+            MintExtensionSpec::TransferFeeConfig { transfer_fee_config_authority, withdraw_withheld_authority, transfer_fee_basis_points, maximum_fee } => {
+                let fee_basis_points = match transfer_fee_basis_points {
+                    Some(basis_points) if *basis_points <= transfer_fee::MAX_FEE_BASIS_POINTS => *basis_points,
+                    Some(_) => return Err(format!("Transfer fee basis points must be between 0 and {}", transfer_fee::MAX_FEE_BASIS_POINTS)),
+                    None => return Err("Please specify the transfer fee in basis points".to_string()),
+                };
+                let maximum_fee_amount = match maximum_fee {
+                    Some(fee_amount) => *fee_amount,
+                    None => return Err("Please specify the maximum transfer fee".to_string()),
+                };
+                transfer_fee::instruction::initialize_transfer_fee_config(
+                    token_program_id,
+                    token_mint_public_key,
+                    parse_optional_pubkey(transfer_fee_config_authority.as_deref())?.as_ref(),
+                    parse_optional_pubkey(withdraw_withheld_authority.as_deref())?.as_ref(),
+                    fee_basis_points,
+                    maximum_fee_amount,
+                )
+            }
+            MintExtensionSpec::MetadataPointer { authority, metadata_address } => {
+                let metadata_account = parse_optional_pubkey(metadata_address.as_deref())?.unwrap_or(*token_mint_public_key);
+                metadata_pointer_address = Some(metadata_account);
+                metadata_pointer::instruction::initialize(
+                    token_program_id,
+                    token_mint_public_key,
+                    parse_optional_pubkey(authority.as_deref())?,
+                    Some(metadata_account),
+                )
+            }
+            MintExtensionSpec::TokenMetadata { name, symbol, uri, update_authority } => {
+                let token_name = match name {
+                    Some(name_text) if !name_text.is_empty() => name_text,
+                    _ => return Err("A token name is required for the token metadata extension".to_string()),
+                };
+                let token_symbol = match symbol {
+                    Some(symbol_text) if !symbol_text.is_empty() => symbol_text,
+                    _ => return Err("A token symbol is required for the token metadata extension".to_string()),
+                };
+                let token_uri = uri.clone().unwrap_or_default();
+                let metadata_update_authority = parse_optional_pubkey(update_authority.as_deref())?.unwrap_or(*mint_authority_public_key);
+
+                let token_metadata = TokenMetadata {
+                    mint: *token_mint_public_key,
+                    name: token_name.clone(),
+                    symbol: token_symbol.clone(),
+                    uri: token_uri.clone(),
+                    ..Default::default()
+                };
+                metadata_space = token_metadata
+                    .tlv_size_of()
+                    .map_err(|_| "Unable to calculate the space needed for the token metadata".to_string())?;
+
+                after_initialize.push(spl_token_metadata_interface::instruction::initialize(
+                    token_program_id,
+                    token_mint_public_key,
+                    &metadata_update_authority,
+                    token_mint_public_key,
+                    mint_authority_public_key,
+                    token_name.clone(),
+                    token_symbol.clone(),
+                    token_uri,
+                ));
+                continue;
+            }
+            MintExtensionSpec::InterestBearingConfig { rate_authority, rate } => {
+                let interest_rate = match rate {
+                    Some(rate_basis_points) => *rate_basis_points,
+                    None => return Err("Please specify the interest rate in basis points".to_string()),
+                };
+                interest_bearing_mint::instruction::initialize(
+                    token_program_id,
+                    token_mint_public_key,
+                    parse_optional_pubkey(rate_authority.as_deref())?,
+                    interest_rate,
+                )
+            }
+            MintExtensionSpec::NonTransferable => token_instruction::initialize_non_transferable_mint(token_program_id, token_mint_public_key),
+            MintExtensionSpec::PermanentDelegate { delegate } => {
+                let delegate_public_key = match parse_optional_pubkey(delegate.as_deref())? {
+                    Some(delegate_key) => delegate_key,
+                    None => return Err("A delegate address is required for the permanent delegate extension".to_string()),
+                };
+                token_instruction::initialize_permanent_delegate(token_program_id, token_mint_public_key, &delegate_public_key)
+            }
+            MintExtensionSpec::MintCloseAuthority { close_authority } => token_instruction::initialize_mint_close_authority(
+                token_program_id,
+                token_mint_public_key,
+                parse_optional_pubkey(close_authority.as_deref())?.as_ref(),
+            ),
+            MintExtensionSpec::DefaultAccountState { state } => {
+                let default_state = match state.as_deref() {
+                    Some("initialized") => AccountState::Initialized,
+                    Some("frozen") => AccountState::Frozen,
+                    _ => return Err("The default account state must be either \"initialized\" or \"frozen\"".to_string()),
+                };
+                default_account_state::instruction::initialize_default_account_state(token_program_id, token_mint_public_key, &default_state)
+            }
+        };
+
+        before_initialize.push(
+            extension_instruction.map_err(|_| format!("Unable to create the {:?} extension instruction", extension_type))?,
+        );
+    }
+
+    if extension_types.contains(&ExtensionType::TokenMetadata) && metadata_pointer_address != Some(*token_mint_public_key) {
+        return Err("Token metadata requires a metadata pointer extension that points to the mint itself".to_string());
+    }
+
+    let fixed_extension_types: Vec<ExtensionType> = extension_types
+        .into_iter()
+        .filter(|extension_type| *extension_type != ExtensionType::TokenMetadata)
+        .collect();
+
+    Ok(MintExtensionInstructions {
+        before_initialize,
+        after_initialize,
+        extension_types: fixed_extension_types,
+        metadata_space,
+    })
+}
+
+pub fn build_create_token_plan(token_creation_request: &CreateTokenRequest) -> Result<AccountCreationPlan, String> {
     let authority_for_new_mint = match &token_creation_request.mint_authority {
         Some(authority_address) if !authority_address.is_empty() => authority_address,
-        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("A mint authority address is required to create a new token".to_string()))),
+        _ => return Err("A mint authority address is required to create a new token".to_string()),
     };
     
     let new_token_mint_address = match &token_creation_request.mint {
         Some(mint_address) if !mint_address.is_empty() => mint_address,
-        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Please provide the address for the new token mint".to_string()))),
+        _ => return Err("Please provide the address for the new token mint".to_string()),
     };
     
     let token_decimal_places = match token_creation_request.decimals {
         Some(decimal_count) if decimal_count <= 9 => decimal_count,
-        Some(_) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Decimals must be between 0 and 9".to_string()))),
-        None => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Please specify the number of decimal places for this token".to_string()))),
+        Some(_) => return Err("Decimals must be between 0 and 9".to_string()),
+        None => return Err("Please specify the number of decimal places for this token".to_string()),
     };
 
-    let mint_authority_public_key = match parse_pubkey(authority_for_new_mint) {
-        Ok(valid_pubkey) => valid_pubkey,
-        Err(parsing_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(parsing_error))),
-    };
-    
-    let token_mint_public_key = match parse_pubkey(new_token_mint_address) {
-        Ok(valid_pubkey) => valid_pubkey,
-        Err(parsing_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(parsing_error))),
-    };
+    let token_program_id = parse_token_program(token_creation_request.program.as_deref())?;
+    let mint_authority_public_key = parse_pubkey(authority_for_new_mint)?;
+    let token_mint_public_key = parse_pubkey(new_token_mint_address)?;
 
     if mint_authority_public_key == solana_program::system_program::id() {
-        return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("The system program cannot be used as a mint authority".to_string())));
+        return Err("The system program cannot be used as a mint authority".to_string());
     }
     
     if token_mint_public_key == solana_program::system_program::id() {
-        return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("The system program cannot be used as a token mint address".to_string())));
+        return Err("The system program cannot be used as a token mint address".to_string());
+    }
+
+    let freeze_authority_public_key = match &token_creation_request.freeze_authority {
+        None => Some(mint_authority_public_key),
+        Some(None) => None,
+        Some(Some(freeze_authority_address)) => Some(parse_pubkey(freeze_authority_address)?),
+    };
+
+    if freeze_authority_public_key == Some(solana_program::system_program::id()) {
+        return Err("The system program cannot be used as a freeze authority".to_string());
     }
 
-    let mint_initialization_instruction = match token_instruction::initialize_mint(
-        &spl_token::id(),
+    let extension_specs = token_creation_request.extensions.as_deref().unwrap_or_default();
+    if !extension_specs.is_empty() && token_program_id != spl_token_2022::id() {
+        return Err("Mint extensions are only available with the token-2022 program".to_string());
+    }
+
+    let defaults_to_frozen_accounts = extension_specs.iter().any(|extension_spec| {
+        matches!(extension_spec, MintExtensionSpec::DefaultAccountState { state } if state.as_deref() == Some("frozen"))
+    });
+    if defaults_to_frozen_accounts && freeze_authority_public_key.is_none() {
+        return Err("A frozen default account state requires a freeze authority".to_string());
+    }
+
+    let mint_extensions = build_mint_extension_instructions(&token_program_id, &token_mint_public_key, &mint_authority_public_key, extension_specs)?;
+
+    let mint_initialization_instruction = token_instruction::initialize_mint(
+        &token_program_id,
         &token_mint_public_key,
         &mint_authority_public_key,
-        Some(&mint_authority_public_key),
+        freeze_authority_public_key.as_ref(),
         token_decimal_places,
+    )
+    .map_err(|_| "Unable to create the token initialization instruction".to_string())?;
+
+    let mint_account_space = ExtensionType::try_calculate_account_len::<Mint>(&mint_extensions.extension_types)
+        .map_err(|_| "Unable to calculate the space needed for the mint account".to_string())?;
+    let rent_parameters = rent_from_schedule(token_creation_request.rent_schedule.as_ref())?;
+    let rent_exempt_lamports = rent_parameters.minimum_balance(mint_account_space + mint_extensions.metadata_space);
+
+    let requested_payer_public_key = parse_optional_pubkey(token_creation_request.payer.as_deref())?;
+    let payer_public_key = requested_payer_public_key.unwrap_or(mint_authority_public_key);
+    if payer_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot pay for the new mint".to_string());
+    }
+
+    let initial_supply = token_creation_request.initial_supply.unwrap_or(0);
+    let multisig_signers = parse_multisig_signers(token_creation_request.multisig_signers.as_deref())?;
+    let multisig_signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    if !multisig_signers.is_empty() && initial_supply == 0 {
+        return Err("Multisig signers are only used to mint the initial supply, so please provide an initialSupply".to_string());
+    }
+    if !multisig_signers.is_empty() && requested_payer_public_key.is_none() {
+        return Err("A multisig mint authority cannot sign to pay for its token account, so please provide a payer".to_string());
+    }
+    // The authority's new token account starts frozen, so the initial mint into it would always fail
+    if defaults_to_frozen_accounts && initial_supply > 0 {
+        return Err("An initial supply cannot be minted when new token accounts default to frozen".to_string());
+    }
+    let create_authority_token_account = token_creation_request.create_authority_ata.unwrap_or(false) || initial_supply > 0;
+
+    let mut creation_instructions = Vec::new();
+    if token_creation_request.create_mint_account.unwrap_or(false) {
+        creation_instructions.push(system_instruction::create_account(
+            &payer_public_key,
+            &token_mint_public_key,
+            rent_exempt_lamports,
+            mint_account_space as u64,
+            &token_program_id,
+        ));
+    }
+    creation_instructions.extend(mint_extensions.before_initialize);
+    creation_instructions.push(mint_initialization_instruction);
+    creation_instructions.extend(mint_extensions.after_initialize);
+
+    if create_authority_token_account {
+        let authority_token_account = spl_associated_token_account::get_associated_token_address_with_program_id(&mint_authority_public_key, &token_mint_public_key, &token_program_id);
+        creation_instructions.push(spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            &payer_public_key,
+            &mint_authority_public_key,
+            &token_mint_public_key,
+            &token_program_id,
+        ));
+
+        if initial_supply > 0 {
+            creation_instructions.push(
+                token_instruction::mint_to(
+                    &token_program_id,
+                    &token_mint_public_key,
+                    &authority_token_account,
+                    &mint_authority_public_key,
+                    &multisig_signer_refs,
+                    initial_supply,
+                )
+                .map_err(|_| "Unable to create the initial supply minting instruction".to_string())?,
+            );
+        }
+    }
+
+    Ok(AccountCreationPlan {
+        instructions: creation_instructions,
+        account_space: mint_account_space,
+        rent_exempt_lamports,
+    })
+}
+
+pub async fn create_token(
+    Json(token_creation_request): Json<CreateTokenRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    let token_creation_plan = match build_create_token_plan(&token_creation_request) {
+        Ok(creation_plan) => creation_plan,
+        Err(validation_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(validation_error))),
+    };
+
+    let mut token_creation_output = match instructions_to_output(
+        token_creation_plan.instructions,
+        &token_creation_request.compute_budget,
+        instruction_to_response,
+        instruction_to_response,
     ) {
-        Ok(created_instruction) => created_instruction,
-        Err(_) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Unable to create the token initialization instruction".to_string()))),
+        Ok(creation_output) => creation_output,
+        Err(validation_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(validation_error))),
     };
 
-    (StatusCode::OK, ResponseJson(ApiResponse::success(instruction_to_response(mint_initialization_instruction))))
+    if let InstructionOutput::Bundle(creation_bundle) = &mut token_creation_output {
+        creation_bundle.account_space = Some(token_creation_plan.account_space);
+        creation_bundle.rent_exempt_lamports = Some(token_creation_plan.rent_exempt_lamports);
+    }
+
+    (StatusCode::OK, ResponseJson(ApiResponse::success(token_creation_output)))
 }
 
-pub async fn mint_token(
-    Json(token_minting_request): Json<MintTokenRequest>,
-) -> (StatusCode, ResponseJson<ApiResponse<InstructionData>>) {
+pub fn build_mint_token_instruction(token_minting_request: &MintTokenRequest) -> Result<Instruction, String> {
     let target_token_mint = match &token_minting_request.mint {
         Some(mint_address) if !mint_address.is_empty() => mint_address,
-        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Please provide the mint address of the token you want to mint".to_string()))),
+        _ => return Err("Please provide the mint address of the token you want to mint".to_string()),
     };
     
     let token_recipient_address = match &token_minting_request.destination {
         Some(recipient_address) if !recipient_address.is_empty() => recipient_address,
-        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("A destination address is required to receive the minted tokens".to_string()))),
+        _ => return Err("A destination address is required to receive the minted tokens".to_string()),
     };
     
     let minting_authority_address = match &token_minting_request.authority {
         Some(authority_address) if !authority_address.is_empty() => authority_address,
-        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("The minting authority address is required to authorize this operation".to_string()))),
+        _ => return Err("The minting authority address is required to authorize this operation".to_string()),
     };
     
-    let tokens_to_mint = match token_minting_request.amount {
-        Some(0) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Amount must be greater than 0".to_string()))),
+    let token_decimal_places = parse_token_decimals(token_minting_request.decimals)?;
+    let requested_mint_amount = token_minting_request
+        .amount
+        .as_ref()
+        .map(|token_amount| token_amount_to_base_units(token_amount, token_decimal_places))
+        .transpose()?;
+
+    let tokens_to_mint = match requested_mint_amount {
+        Some(0) => return Err("Amount must be greater than 0".to_string()),
         Some(mint_amount) if mint_amount > 0 => mint_amount,
-        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Please specify how many tokens you want to mint".to_string()))),
+        _ => return Err("Please specify how many tokens you want to mint".to_string()),
     };
 
-    let token_mint_public_key = match parse_pubkey(target_token_mint) {
-        Ok(valid_pubkey) => valid_pubkey,
-        Err(parsing_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(parsing_error))),
-    };
-    
-    let recipient_public_key = match parse_pubkey(token_recipient_address) {
-        Ok(valid_pubkey) => valid_pubkey,
-        Err(parsing_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(parsing_error))),
-    };
-    
-    let minting_authority_public_key = match parse_pubkey(minting_authority_address) {
-        Ok(valid_pubkey) => valid_pubkey,
-        Err(parsing_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(parsing_error))),
-    };
+    let token_program_id = parse_token_program(token_minting_request.program.as_deref())?;
+    let multisig_signers = parse_multisig_signers(token_minting_request.multisig_signers.as_deref())?;
+    let multisig_signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    let token_mint_public_key = parse_pubkey(target_token_mint)?;
+    let recipient_public_key = parse_pubkey(token_recipient_address)?;
+    let minting_authority_public_key = parse_pubkey(minting_authority_address)?;
 
     if token_mint_public_key == solana_program::system_program::id() {
-        return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("The system program cannot be used as a token mint".to_string())));
+        return Err("The system program cannot be used as a token mint".to_string());
     }
     
     if recipient_public_key == solana_program::system_program::id() {
-        return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Tokens cannot be minted directly to the system program".to_string())));
+        return Err("Tokens cannot be minted directly to the system program".to_string());
     }
     
     if minting_authority_public_key == solana_program::system_program::id() {
-        return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("The system program cannot serve as a minting authority".to_string())));
+        return Err("The system program cannot serve as a minting authority".to_string());
     }
 
-    let token_minting_instruction = match token_instruction::mint_to(
-        &spl_token::id(),
-        &token_mint_public_key,
-        &recipient_public_key,
-        &minting_authority_public_key,
-        &[],
-        tokens_to_mint,
-    ) {
-        Ok(created_instruction) => created_instruction,
-        Err(_) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Unable to create the token minting instruction".to_string()))),
+    let token_minting_instruction = match token_decimal_places {
+        Some(decimal_count) => token_instruction::mint_to_checked(
+            &token_program_id,
+            &token_mint_public_key,
+            &recipient_public_key,
+            &minting_authority_public_key,
+            &multisig_signer_refs,
+            tokens_to_mint,
+            decimal_count,
+        ),
+        None => token_instruction::mint_to(
+            &token_program_id,
+            &token_mint_public_key,
+            &recipient_public_key,
+            &minting_authority_public_key,
+            &multisig_signer_refs,
+            tokens_to_mint,
+        ),
     };
 
-    (StatusCode::OK, ResponseJson(ApiResponse::success(instruction_to_response(token_minting_instruction))))
-}
\ No newline at end of file
+    token_minting_instruction.map_err(|_| "Unable to create the token minting instruction".to_string())
+}
+
+pub async fn mint_token(
+    Json(token_minting_request): Json<MintTokenRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_mint_token_instruction(&token_minting_request).map(|instruction| vec![instruction]), &token_minting_request.compute_budget)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn create_token_request(request_json: serde_json::Value) -> CreateTokenRequest {
+        serde_json::from_value(request_json).unwrap()
+    }
+
+    fn creation_error(request_json: serde_json::Value) -> String {
+        build_create_token_plan(&create_token_request(request_json)).err().unwrap()
+    }
+
+    #[test]
+    fn initial_supply_is_rejected_when_accounts_default_to_frozen() {
+        let frozen_mint_request = json!({
+            "mintAuthority": Pubkey::new_unique().to_string(),
+            "mint": Pubkey::new_unique().to_string(),
+            "decimals": 6,
+            "program": "token-2022",
+            "extensions": [{ "type": "defaultAccountState", "state": "frozen" }],
+            "initialSupply": 1_000,
+        });
+        assert!(creation_error(frozen_mint_request.clone()).contains("default to frozen"));
+
+        let mut frozen_mint_without_supply = frozen_mint_request;
+        frozen_mint_without_supply["initialSupply"] = json!(0);
+        frozen_mint_without_supply["createAuthorityAta"] = json!(true);
+        let creation_plan = build_create_token_plan(&create_token_request(frozen_mint_without_supply)).unwrap();
+        assert_eq!(creation_plan.instructions.len(), 3);
+    }
+
+    #[test]
+    fn multisig_mint_authority_needs_an_explicit_payer() {
+        let mint_authority = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let mut multisig_mint_request = json!({
+            "mintAuthority": mint_authority.to_string(),
+            "mint": Pubkey::new_unique().to_string(),
+            "decimals": 6,
+            "initialSupply": 1_000,
+            "multisigSigners": [Pubkey::new_unique().to_string(), Pubkey::new_unique().to_string()],
+        });
+        assert!(creation_error(multisig_mint_request.clone()).contains("please provide a payer"));
+
+        multisig_mint_request["payer"] = json!(payer.to_string());
+        let creation_plan = build_create_token_plan(&create_token_request(multisig_mint_request)).unwrap();
+        let ata_creation_instruction = &creation_plan.instructions[1];
+        assert_eq!(ata_creation_instruction.accounts[0].pubkey, payer);
+        assert!(ata_creation_instruction.accounts.iter().all(|account_meta| account_meta.pubkey != mint_authority || !account_meta.is_signer));
+    }
+}
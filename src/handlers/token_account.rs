@@ -0,0 +1,266 @@
+use axum::{extract::Json, http::StatusCode, response::Json as ResponseJson};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use spl_token_2022::instruction as token_instruction;
+
+use crate::models::{
+    ApiResponse, ApproveTokenRequest, BurnTokenRequest, CloseAccountRequest, FreezeAccountRequest,
+    InstructionData, InstructionOutput, RevokeTokenRequest,
+};
+use crate::utils::{
+    instructions_result_to_response, parse_multisig_signers, parse_optional_pubkey, parse_pubkey, parse_token_decimals,
+    parse_token_program, resolve_token_account, token_amount_to_base_units,
+};
+
+pub fn build_burn_token_instruction(burn_request: &BurnTokenRequest) -> Result<Instruction, String> {
+    let token_mint_address = match &burn_request.mint {
+        Some(mint_address) if !mint_address.is_empty() => mint_address,
+        _ => return Err("Please provide the mint address of the token you want to burn".to_string()),
+    };
+
+    let token_owner_address = match &burn_request.owner {
+        Some(owner_address) if !owner_address.is_empty() => owner_address,
+        _ => return Err("The token owner address is required to authorize the burn".to_string()),
+    };
+
+    let token_decimal_places = parse_token_decimals(burn_request.decimals)?;
+    let requested_burn_amount = burn_request
+        .amount
+        .as_ref()
+        .map(|token_amount| token_amount_to_base_units(token_amount, token_decimal_places))
+        .transpose()?;
+
+    let tokens_to_burn = match requested_burn_amount {
+        Some(0) => return Err("Amount must be greater than 0".to_string()),
+        Some(burn_amount) => burn_amount,
+        None => return Err("Please specify how many tokens you want to burn".to_string()),
+    };
+
+    let token_program_id = parse_token_program(burn_request.program.as_deref())?;
+    let multisig_signers = parse_multisig_signers(burn_request.multisig_signers.as_deref())?;
+    let multisig_signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    let token_mint_public_key = parse_pubkey(token_mint_address)?;
+    let owner_public_key = parse_pubkey(token_owner_address)?;
+
+    if token_mint_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot be used as a token mint".to_string());
+    }
+
+    if owner_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot serve as a token owner".to_string());
+    }
+
+    let token_account = resolve_token_account(burn_request.account.as_deref(), &owner_public_key, Some(&token_mint_public_key), &token_program_id)?;
+
+    let token_burning_instruction = match token_decimal_places {
+        Some(decimal_count) => token_instruction::burn_checked(
+            &token_program_id,
+            &token_account,
+            &token_mint_public_key,
+            &owner_public_key,
+            &multisig_signer_refs,
+            tokens_to_burn,
+            decimal_count,
+        ),
+        None => token_instruction::burn(
+            &token_program_id,
+            &token_account,
+            &token_mint_public_key,
+            &owner_public_key,
+            &multisig_signer_refs,
+            tokens_to_burn,
+        ),
+    };
+
+    token_burning_instruction.map_err(|_| "Unable to create the token burn instruction".to_string())
+}
+
+pub async fn burn_token(
+    Json(burn_request): Json<BurnTokenRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_burn_token_instruction(&burn_request).map(|instruction| vec![instruction]), &burn_request.compute_budget)
+}
+
+pub fn build_approve_token_instruction(approve_request: &ApproveTokenRequest) -> Result<Instruction, String> {
+    let token_owner_address = match &approve_request.owner {
+        Some(owner_address) if !owner_address.is_empty() => owner_address,
+        _ => return Err("The token owner address is required to approve a delegate".to_string()),
+    };
+
+    let delegate_address = match &approve_request.delegate {
+        Some(delegate_address) if !delegate_address.is_empty() => delegate_address,
+        _ => return Err("Please provide the delegate address you want to approve".to_string()),
+    };
+
+    let token_decimal_places = parse_token_decimals(approve_request.decimals)?;
+    let requested_allowance = approve_request
+        .amount
+        .as_ref()
+        .map(|token_amount| token_amount_to_base_units(token_amount, token_decimal_places))
+        .transpose()?;
+
+    let delegated_amount = match requested_allowance {
+        Some(0) => return Err("Amount must be greater than 0".to_string()),
+        Some(allowance) => allowance,
+        None => return Err("Please specify how many tokens the delegate may transfer".to_string()),
+    };
+
+    let token_program_id = parse_token_program(approve_request.program.as_deref())?;
+    let multisig_signers = parse_multisig_signers(approve_request.multisig_signers.as_deref())?;
+    let multisig_signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    let token_mint_public_key = parse_optional_pubkey(approve_request.mint.as_deref())?;
+    let owner_public_key = parse_pubkey(token_owner_address)?;
+    let delegate_public_key = parse_pubkey(delegate_address)?;
+
+    if owner_public_key == delegate_public_key {
+        return Err("The token owner cannot approve themselves as a delegate".to_string());
+    }
+
+    if owner_public_key == solana_program::system_program::id() || delegate_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot be used as a token owner or delegate".to_string());
+    }
+
+    let token_account = resolve_token_account(approve_request.account.as_deref(), &owner_public_key, token_mint_public_key.as_ref(), &token_program_id)?;
+
+    let approval_instruction = match (token_decimal_places, token_mint_public_key) {
+        (Some(decimal_count), Some(mint_public_key)) => token_instruction::approve_checked(
+            &token_program_id,
+            &token_account,
+            &mint_public_key,
+            &delegate_public_key,
+            &owner_public_key,
+            &multisig_signer_refs,
+            delegated_amount,
+            decimal_count,
+        ),
+        (Some(_), None) => return Err("The mint address is required for a checked approval".to_string()),
+        (None, _) => token_instruction::approve(
+            &token_program_id,
+            &token_account,
+            &delegate_public_key,
+            &owner_public_key,
+            &multisig_signer_refs,
+            delegated_amount,
+        ),
+    };
+
+    approval_instruction.map_err(|_| "Unable to create the delegate approval instruction".to_string())
+}
+
+pub async fn approve_token(
+    Json(approve_request): Json<ApproveTokenRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_approve_token_instruction(&approve_request).map(|instruction| vec![instruction]), &approve_request.compute_budget)
+}
+
+pub fn build_revoke_token_instruction(revoke_request: &RevokeTokenRequest) -> Result<Instruction, String> {
+    let token_owner_address = match &revoke_request.owner {
+        Some(owner_address) if !owner_address.is_empty() => owner_address,
+        _ => return Err("The token owner address is required to revoke a delegate".to_string()),
+    };
+
+    let token_program_id = parse_token_program(revoke_request.program.as_deref())?;
+    let multisig_signers = parse_multisig_signers(revoke_request.multisig_signers.as_deref())?;
+    let multisig_signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    let token_mint_public_key = parse_optional_pubkey(revoke_request.mint.as_deref())?;
+    let owner_public_key = parse_pubkey(token_owner_address)?;
+
+    if owner_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot serve as a token owner".to_string());
+    }
+
+    let token_account = resolve_token_account(revoke_request.account.as_deref(), &owner_public_key, token_mint_public_key.as_ref(), &token_program_id)?;
+
+    token_instruction::revoke(&token_program_id, &token_account, &owner_public_key, &multisig_signer_refs)
+        .map_err(|_| "Unable to create the delegate revocation instruction".to_string())
+}
+
+pub async fn revoke_token(
+    Json(revoke_request): Json<RevokeTokenRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_revoke_token_instruction(&revoke_request).map(|instruction| vec![instruction]), &revoke_request.compute_budget)
+}
+
+pub fn build_freeze_account_instruction(freeze_request: &FreezeAccountRequest, freeze: bool) -> Result<Instruction, String> {
+    let token_mint_address = match &freeze_request.mint {
+        Some(mint_address) if !mint_address.is_empty() => mint_address,
+        _ => return Err("Please provide the mint address of the token account".to_string()),
+    };
+
+    let freeze_authority_address = match &freeze_request.authority {
+        Some(authority_address) if !authority_address.is_empty() => authority_address,
+        _ => return Err("The freeze authority address is required for this operation".to_string()),
+    };
+
+    let token_program_id = parse_token_program(freeze_request.program.as_deref())?;
+    let multisig_signers = parse_multisig_signers(freeze_request.multisig_signers.as_deref())?;
+    let multisig_signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    let token_mint_public_key = parse_pubkey(token_mint_address)?;
+    let freeze_authority_public_key = parse_pubkey(freeze_authority_address)?;
+
+    if token_mint_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot be used as a token mint".to_string());
+    }
+
+    if freeze_authority_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot serve as a freeze authority".to_string());
+    }
+
+    let token_account = match (parse_optional_pubkey(freeze_request.account.as_deref())?, parse_optional_pubkey(freeze_request.owner.as_deref())?) {
+        (Some(token_account), _) => token_account,
+        (None, Some(owner_public_key)) => resolve_token_account(None, &owner_public_key, Some(&token_mint_public_key), &token_program_id)?,
+        (None, None) => return Err("Please provide either the token account or its owner".to_string()),
+    };
+
+    let freeze_instruction = if freeze {
+        token_instruction::freeze_account(&token_program_id, &token_account, &token_mint_public_key, &freeze_authority_public_key, &multisig_signer_refs)
+    } else {
+        token_instruction::thaw_account(&token_program_id, &token_account, &token_mint_public_key, &freeze_authority_public_key, &multisig_signer_refs)
+    };
+
+    freeze_instruction.map_err(|_| "Unable to create the account freeze instruction".to_string())
+}
+
+pub async fn freeze_token_account(
+    Json(freeze_request): Json<FreezeAccountRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_freeze_account_instruction(&freeze_request, true).map(|instruction| vec![instruction]), &freeze_request.compute_budget)
+}
+
+pub async fn thaw_token_account(
+    Json(thaw_request): Json<FreezeAccountRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_freeze_account_instruction(&thaw_request, false).map(|instruction| vec![instruction]), &thaw_request.compute_budget)
+}
+
+pub fn build_close_account_instruction(close_request: &CloseAccountRequest) -> Result<Instruction, String> {
+    let token_owner_address = match &close_request.owner {
+        Some(owner_address) if !owner_address.is_empty() => owner_address,
+        _ => return Err("The token owner address is required to close the account".to_string()),
+    };
+
+    let token_program_id = parse_token_program(close_request.program.as_deref())?;
+    let multisig_signers = parse_multisig_signers(close_request.multisig_signers.as_deref())?;
+    let multisig_signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    let token_mint_public_key = parse_optional_pubkey(close_request.mint.as_deref())?;
+    let owner_public_key = parse_pubkey(token_owner_address)?;
+    let lamport_destination = parse_optional_pubkey(close_request.destination.as_deref())?.unwrap_or(owner_public_key);
+
+    if owner_public_key == solana_program::system_program::id() || lamport_destination == solana_program::system_program::id() {
+        return Err("Closing accounts involving the system program is not allowed".to_string());
+    }
+
+    let token_account = resolve_token_account(close_request.account.as_deref(), &owner_public_key, token_mint_public_key.as_ref(), &token_program_id)?;
+
+    if token_account == lamport_destination {
+        return Err("A token account cannot be closed into itself".to_string());
+    }
+
+    token_instruction::close_account(&token_program_id, &token_account, &lamport_destination, &owner_public_key, &multisig_signer_refs)
+        .map_err(|_| "Unable to create the account closing instruction".to_string())
+}
+
+pub async fn close_token_account(
+    Json(close_request): Json<CloseAccountRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_close_account_instruction(&close_request).map(|instruction| vec![instruction]), &close_request.compute_budget)
+}
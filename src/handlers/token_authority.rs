@@ -0,0 +1,283 @@
+use axum::{extract::Json, http::StatusCode, response::Json as ResponseJson};
+use solana_program::{instruction::Instruction, program_pack::Pack, pubkey::Pubkey, system_instruction};
+use spl_token_2022::{
+    instruction::{self as token_instruction, AuthorityType},
+    state::Multisig,
+};
+
+use crate::handlers::AccountCreationPlan;
+use crate::models::{
+    ApiResponse, CreateMultisigRequest, InstructionBundleData, SetAuthorityData, SetAuthorityRequest,
+};
+use crate::utils::{
+    instruction_to_response, instructions_to_bundle, instructions_to_output, parse_multisig_signers, parse_pubkey, parse_token_program, rent_from_schedule,
+    result_to_response,
+};
+
+fn parse_authority_type(authority_type_name: &str) -> Result<AuthorityType, String> {
+    match authority_type_name {
+        "mintTokens" => Ok(AuthorityType::MintTokens),
+        "freezeAccount" => Ok(AuthorityType::FreezeAccount),
+        "accountOwner" => Ok(AuthorityType::AccountOwner),
+        "closeAccount" => Ok(AuthorityType::CloseAccount),
+        _ => Err("The authority type must be one of \"mintTokens\", \"freezeAccount\", \"accountOwner\" or \"closeAccount\"".to_string()),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum AuthorityTarget {
+    Mint,
+    TokenAccount,
+}
+
+struct SetAuthorityPlan {
+    instruction: Instruction,
+    warnings: Vec<String>,
+}
+
+// Only a close authority can sit on either kind of account, and only Token-2022 mints have one
+fn resolve_authority_target(account_type_name: Option<&str>, authority_type: &AuthorityType, token_program_id: &Pubkey) -> Result<AuthorityTarget, String> {
+    let requested_target = match account_type_name {
+        None => None,
+        Some("mint") => Some(AuthorityTarget::Mint),
+        Some("tokenAccount") => Some(AuthorityTarget::TokenAccount),
+        Some(_) => return Err("The account type must be either \"mint\" or \"tokenAccount\"".to_string()),
+    };
+
+    let required_target = match authority_type {
+        AuthorityType::MintTokens | AuthorityType::FreezeAccount => AuthorityTarget::Mint,
+        AuthorityType::CloseAccount if *token_program_id == spl_token_2022::id() => {
+            return requested_target.ok_or_else(|| {
+                "A Token-2022 close authority can belong to a mint or a token account, so please set accountType to \"mint\" or \"tokenAccount\"".to_string()
+            });
+        }
+        _ => AuthorityTarget::TokenAccount,
+    };
+
+    match requested_target {
+        Some(requested_target) if requested_target != required_target => Err(match required_target {
+            AuthorityTarget::Mint => "This authority type can only be changed on a mint".to_string(),
+            AuthorityTarget::TokenAccount => "This authority type can only be changed on a token account".to_string(),
+        }),
+        _ => Ok(required_target),
+    }
+}
+
+fn authority_change_warnings(authority_type: &AuthorityType, authority_target: AuthorityTarget, new_authority_public_key: Option<&Pubkey>) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    match (authority_type, authority_target, new_authority_public_key) {
+        (AuthorityType::MintTokens, _, None) => warnings.push("Revoking the mint authority is permanent: no more tokens can ever be minted and the supply is fixed".to_string()),
+        (AuthorityType::FreezeAccount, _, None) => warnings.push("Revoking the freeze authority is permanent: token accounts for this mint can never be frozen or thawed again".to_string()),
+        (AuthorityType::CloseAccount, AuthorityTarget::Mint, None) => {
+            warnings.push("Revoking the mint close authority is permanent: this mint can never be closed and its rent can never be reclaimed".to_string())
+        }
+        (AuthorityType::CloseAccount, AuthorityTarget::TokenAccount, None) => warnings.push("Removing the close authority returns the right to close this account to its owner".to_string()),
+        (AuthorityType::AccountOwner, _, Some(_)) => warnings.push(
+            "Changing the account owner clears any delegate on the token account, and on a wrapped SOL account it also clears the close authority".to_string(),
+        ),
+        _ => {}
+    }
+
+    if let Some(new_authority) = new_authority_public_key {
+        warnings.push(format!("After this change only {} can sign for this authority; the current authority cannot undo it", new_authority));
+    }
+
+    warnings
+}
+
+fn build_set_authority_plan(authority_request: &SetAuthorityRequest) -> Result<SetAuthorityPlan, String> {
+    let owned_account_address = match &authority_request.account {
+        Some(account_address) if !account_address.is_empty() => account_address,
+        _ => return Err("Please provide the mint or token account whose authority you want to change".to_string()),
+    };
+
+    let authority_type_name = match &authority_request.authority_type {
+        Some(type_name) if !type_name.is_empty() => type_name,
+        _ => return Err("Please specify which authority type you want to change".to_string()),
+    };
+
+    let current_authority_address = match &authority_request.current_authority {
+        Some(authority_address) if !authority_address.is_empty() => authority_address,
+        _ => return Err("The current authority address is required to sign the change".to_string()),
+    };
+
+    let new_authority_address = match &authority_request.new_authority {
+        Some(new_authority) => new_authority.as_deref(),
+        None => return Err("Please provide the new authority, or null to revoke it permanently".to_string()),
+    };
+
+    let multisig_signers = parse_multisig_signers(authority_request.multisig_signers.as_deref())?;
+    let multisig_signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    let authority_type = parse_authority_type(authority_type_name)?;
+    let token_program_id = parse_token_program(authority_request.program.as_deref())?;
+    let authority_target = resolve_authority_target(authority_request.account_type.as_deref(), &authority_type, &token_program_id)?;
+    let owned_account_public_key = parse_pubkey(owned_account_address)?;
+    let current_authority_public_key = parse_pubkey(current_authority_address)?;
+    let new_authority_public_key = new_authority_address.map(parse_pubkey).transpose()?;
+
+    if owned_account_public_key == solana_program::system_program::id() || current_authority_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot be used as the account or its current authority".to_string());
+    }
+
+    if new_authority_public_key == Some(solana_program::system_program::id()) {
+        return Err("The system program cannot be used as a new authority; pass null to revoke instead".to_string());
+    }
+
+    if new_authority_public_key == Some(current_authority_public_key) {
+        return Err("The new authority is the same as the current authority".to_string());
+    }
+
+    if matches!(authority_type, AuthorityType::AccountOwner) && new_authority_public_key.is_none() {
+        return Err("A token account must always have an owner, so the account owner cannot be revoked".to_string());
+    }
+
+    let warnings = authority_change_warnings(&authority_type, authority_target, new_authority_public_key.as_ref());
+    let set_authority_instruction = token_instruction::set_authority(
+        &token_program_id,
+        &owned_account_public_key,
+        new_authority_public_key.as_ref(),
+        authority_type,
+        &current_authority_public_key,
+        &multisig_signer_refs,
+    )
+    .map_err(|_| "Unable to create the set authority instruction".to_string())?;
+
+    Ok(SetAuthorityPlan {
+        instruction: set_authority_instruction,
+        warnings,
+    })
+}
+
+pub fn build_set_authority_instruction(authority_request: &SetAuthorityRequest) -> Result<Instruction, String> {
+    build_set_authority_plan(authority_request).map(|authority_plan| authority_plan.instruction)
+}
+
+pub async fn set_token_authority(
+    Json(authority_request): Json<SetAuthorityRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<SetAuthorityData>>) {
+    result_to_response(build_set_authority_plan(&authority_request).and_then(|authority_plan| {
+        instructions_to_output(vec![authority_plan.instruction], &authority_request.compute_budget, instruction_to_response, instruction_to_response)
+            .map(|authority_output| SetAuthorityData { instruction: authority_output, warnings: authority_plan.warnings })
+    }))
+}
+
+pub fn build_create_multisig_plan(multisig_request: &CreateMultisigRequest) -> Result<AccountCreationPlan, String> {
+    let multisig_address = match &multisig_request.multisig {
+        Some(account_address) if !account_address.is_empty() => account_address,
+        _ => return Err("Please provide the address for the new multisig account".to_string()),
+    };
+
+    let payer_address = match &multisig_request.payer {
+        Some(payer_address) if !payer_address.is_empty() => payer_address,
+        _ => return Err("A payer address is required to fund the multisig account".to_string()),
+    };
+
+    let multisig_signers = parse_multisig_signers(multisig_request.signers.as_deref())?;
+    if multisig_signers.is_empty() {
+        return Err("Please provide the signers of the multisig".to_string());
+    }
+
+    let required_signatures = match multisig_request.threshold {
+        Some(0) => return Err("The multisig threshold must be at least 1".to_string()),
+        Some(threshold) if threshold as usize > multisig_signers.len() => {
+            return Err(format!("The multisig threshold cannot exceed the {} signers provided", multisig_signers.len()));
+        }
+        Some(threshold) => threshold,
+        None => return Err("Please specify how many signatures the multisig requires".to_string()),
+    };
+
+    let token_program_id = parse_token_program(multisig_request.program.as_deref())?;
+    let multisig_public_key = parse_pubkey(multisig_address)?;
+    let payer_public_key = parse_pubkey(payer_address)?;
+
+    if multisig_public_key == solana_program::system_program::id() || payer_public_key == solana_program::system_program::id() {
+        return Err("The system program cannot be used as the multisig or its payer".to_string());
+    }
+
+    if multisig_signers.contains(&multisig_public_key) {
+        return Err("A multisig cannot list itself as one of its signers".to_string());
+    }
+
+    let rent_parameters = rent_from_schedule(multisig_request.rent_schedule.as_ref())?;
+    let rent_exempt_lamports = rent_parameters.minimum_balance(Multisig::LEN);
+    let multisig_signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+
+    let multisig_initialization_instruction = token_instruction::initialize_multisig(&token_program_id, &multisig_public_key, &multisig_signer_refs, required_signatures)
+        .map_err(|_| "Unable to create the multisig initialization instruction".to_string())?;
+
+    Ok(AccountCreationPlan {
+        instructions: vec![
+            system_instruction::create_account(&payer_public_key, &multisig_public_key, rent_exempt_lamports, Multisig::LEN as u64, &token_program_id),
+            multisig_initialization_instruction,
+        ],
+        account_space: Multisig::LEN,
+        rent_exempt_lamports,
+    })
+}
+
+pub async fn create_token_multisig(
+    Json(multisig_request): Json<CreateMultisigRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionBundleData>>) {
+    let multisig_bundle = build_create_multisig_plan(&multisig_request).and_then(|multisig_plan| {
+        let mut multisig_bundle = instructions_to_bundle(multisig_plan.instructions, &multisig_request.compute_budget, instruction_to_response)?;
+        multisig_bundle.account_space = Some(multisig_plan.account_space);
+        multisig_bundle.rent_exempt_lamports = Some(multisig_plan.rent_exempt_lamports);
+        Ok(multisig_bundle)
+    });
+
+    result_to_response(multisig_bundle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn set_authority_plan(authority_type: &str, program: &str, account_type: Option<&str>, new_authority: Option<Pubkey>) -> Result<SetAuthorityPlan, String> {
+        let authority_request: SetAuthorityRequest = serde_json::from_value(json!({
+            "account": Pubkey::new_unique().to_string(),
+            "accountType": account_type,
+            "authorityType": authority_type,
+            "currentAuthority": Pubkey::new_unique().to_string(),
+            "newAuthority": new_authority.map(|new_authority| new_authority.to_string()),
+            "program": program,
+        }))
+        .unwrap();
+        build_set_authority_plan(&authority_request)
+    }
+
+    #[test]
+    fn revoking_a_mint_close_authority_warns_that_it_is_permanent() {
+        let mint_plan = set_authority_plan("closeAccount", "token-2022", Some("mint"), None).unwrap();
+        assert!(mint_plan.warnings[0].starts_with("Revoking the mint close authority is permanent"));
+
+        let token_account_plan = set_authority_plan("closeAccount", "token-2022", Some("tokenAccount"), None).unwrap();
+        assert!(token_account_plan.warnings[0].contains("returns the right to close this account to its owner"));
+
+        let spl_token_plan = set_authority_plan("closeAccount", "spl-token", None, None).unwrap();
+        assert!(spl_token_plan.warnings[0].contains("returns the right to close this account to its owner"));
+    }
+
+    #[test]
+    fn close_authority_target_must_be_known_and_possible() {
+        assert!(set_authority_plan("closeAccount", "token-2022", None, None).err().unwrap().contains("accountType"));
+        assert!(set_authority_plan("closeAccount", "spl-token", Some("mint"), None).is_err());
+        assert!(set_authority_plan("mintTokens", "spl-token", Some("tokenAccount"), None).is_err());
+        assert!(set_authority_plan("accountOwner", "spl-token", Some("mint"), Some(Pubkey::new_unique())).is_err());
+        assert!(set_authority_plan("mintTokens", "spl-token", Some("account"), None).is_err());
+    }
+
+    #[test]
+    fn owner_change_warning_only_promises_to_clear_the_delegate() {
+        let new_owner = Pubkey::new_unique();
+        let owner_plan = set_authority_plan("accountOwner", "spl-token", None, Some(new_owner)).unwrap();
+        assert_eq!(
+            owner_plan.warnings,
+            vec![
+                "Changing the account owner clears any delegate on the token account, and on a wrapped SOL account it also clears the close authority".to_string(),
+                format!("After this change only {} can sign for this authority; the current authority cannot undo it", new_owner),
+            ]
+        );
+    }
+}
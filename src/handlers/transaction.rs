@@ -0,0 +1,426 @@
+use axum::{extract::Json, http::StatusCode, response::Json as ResponseJson};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64_STANDARD};
+use solana_program::{
+    address_lookup_table::AddressLookupTableAccount,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{Message, VersionedMessage, v0},
+    pubkey::Pubkey,
+    system_instruction,
+};
+use solana_sdk::{
+    packet::PACKET_DATA_SIZE,
+    signature::Signature,
+    transaction::{Transaction, VersionedTransaction},
+};
+
+use crate::handlers::{
+    build_advance_nonce_account_instruction, build_approve_token_instruction, build_authorize_nonce_account_instruction,
+    build_burn_token_instruction, build_close_account_instruction, build_create_multisig_plan,
+    build_allocate_system_account_plan, build_assign_system_account_plan, build_create_nonce_account_plan,
+    build_create_stake_account_plan, build_create_system_account_plan, build_create_token_plan,
+    build_deactivate_stake_instruction, build_delegate_stake_instruction, build_freeze_account_instruction,
+    build_merge_stake_instructions, build_mint_token_instruction, build_revoke_token_instruction,
+    build_set_authority_instruction, build_sol_transfer_instructions, build_split_stake_plan,
+    build_standalone_memo_instruction, build_token_transfer_instructions, build_unwrap_sol_instruction,
+    build_withdraw_nonce_account_instruction, build_withdraw_stake_instruction, build_wrap_sol_instructions,
+    parse_nonce_account_and_authority,
+};
+use crate::models::{
+    AddressLookupTableSpec, AddressTableLookupData, ApiResponse, BuildTransactionRequest, ComputeBudgetSpec,
+    InstructionSpec, RawInstructionSpec, TransactionData,
+};
+use crate::utils::{parse_blockhash, parse_pubkey, prepend_compute_budget_instructions};
+
+pub fn parse_raw_instruction(raw_instruction: &RawInstructionSpec) -> Result<Instruction, String> {
+    let program_address = match &raw_instruction.program_id {
+        Some(program_string) if !program_string.is_empty() => program_string,
+        _ => return Err("A program id is required for every raw instruction".to_string()),
+    };
+
+    let encoded_instruction_data = raw_instruction.instruction_data.as_deref().unwrap_or_default();
+
+    let program_public_key = parse_pubkey(program_address)?;
+    let instruction_bytes = bs58::decode(encoded_instruction_data)
+        .into_vec()
+        .map_err(|_| "The instruction data is not in valid base58 format".to_string())?;
+
+    let mut account_metas = Vec::new();
+    for raw_account in raw_instruction.accounts.iter().flatten() {
+        let account_address = match &raw_account.pubkey {
+            Some(account_string) if !account_string.is_empty() => account_string,
+            _ => return Err("Every instruction account needs a pubkey".to_string()),
+        };
+        account_metas.push(AccountMeta {
+            pubkey: parse_pubkey(account_address)?,
+            is_signer: raw_account.is_signer,
+            is_writable: raw_account.is_writable,
+        });
+    }
+
+    Ok(Instruction::new_with_bytes(program_public_key, &instruction_bytes, account_metas))
+}
+
+pub fn build_spec_instructions(instruction_spec: &InstructionSpec) -> Result<Vec<Instruction>, String> {
+    match instruction_spec {
+        InstructionSpec::CreateToken(token_creation_request) => build_create_token_plan(token_creation_request).map(|creation_plan| creation_plan.instructions),
+        InstructionSpec::MintToken(token_minting_request) => build_mint_token_instruction(token_minting_request).map(|instruction| vec![instruction]),
+        InstructionSpec::SendSol(transfer_request) => build_sol_transfer_instructions(transfer_request),
+        InstructionSpec::SendToken(token_request) => build_token_transfer_instructions(token_request),
+        InstructionSpec::BurnToken(burn_request) => build_burn_token_instruction(burn_request).map(|instruction| vec![instruction]),
+        InstructionSpec::ApproveToken(approve_request) => build_approve_token_instruction(approve_request).map(|instruction| vec![instruction]),
+        InstructionSpec::RevokeToken(revoke_request) => build_revoke_token_instruction(revoke_request).map(|instruction| vec![instruction]),
+        InstructionSpec::FreezeAccount(freeze_request) => build_freeze_account_instruction(freeze_request, true).map(|instruction| vec![instruction]),
+        InstructionSpec::ThawAccount(thaw_request) => build_freeze_account_instruction(thaw_request, false).map(|instruction| vec![instruction]),
+        InstructionSpec::CloseAccount(close_request) => build_close_account_instruction(close_request).map(|instruction| vec![instruction]),
+        InstructionSpec::SetAuthority(authority_request) => build_set_authority_instruction(authority_request).map(|instruction| vec![instruction]),
+        InstructionSpec::CreateMultisig(multisig_request) => build_create_multisig_plan(multisig_request).map(|multisig_plan| multisig_plan.instructions),
+        InstructionSpec::WrapSol(wrap_request) => build_wrap_sol_instructions(wrap_request),
+        InstructionSpec::UnwrapSol(unwrap_request) => build_unwrap_sol_instruction(unwrap_request).map(|instruction| vec![instruction]),
+        InstructionSpec::Memo(memo_request) => build_standalone_memo_instruction(memo_request).map(|instruction| vec![instruction]),
+        InstructionSpec::CreateNonceAccount(nonce_request) => build_create_nonce_account_plan(nonce_request).map(|nonce_plan| nonce_plan.instructions),
+        InstructionSpec::AdvanceNonceAccount(advance_request) => build_advance_nonce_account_instruction(advance_request).map(|instruction| vec![instruction]),
+        InstructionSpec::WithdrawNonceAccount(withdraw_request) => build_withdraw_nonce_account_instruction(withdraw_request).map(|instruction| vec![instruction]),
+        InstructionSpec::AuthorizeNonceAccount(authorize_request) => build_authorize_nonce_account_instruction(authorize_request).map(|instruction| vec![instruction]),
+        InstructionSpec::CreateStakeAccount(stake_request) => build_create_stake_account_plan(stake_request).map(|stake_plan| stake_plan.instructions),
+        InstructionSpec::DelegateStake(delegate_request) => build_delegate_stake_instruction(delegate_request).map(|instruction| vec![instruction]),
+        InstructionSpec::DeactivateStake(deactivate_request) => build_deactivate_stake_instruction(deactivate_request).map(|instruction| vec![instruction]),
+        InstructionSpec::WithdrawStake(withdraw_request) => build_withdraw_stake_instruction(withdraw_request).map(|instruction| vec![instruction]),
+        InstructionSpec::SplitStake(split_request) => build_split_stake_plan(split_request).map(|split_plan| split_plan.instructions),
+        InstructionSpec::MergeStake(merge_request) => build_merge_stake_instructions(merge_request),
+        InstructionSpec::CreateSystemAccount(account_request) => build_create_system_account_plan(account_request, false).map(|system_plan| vec![system_plan.instruction]),
+        InstructionSpec::CreateSystemAccountWithSeed(account_request) => build_create_system_account_plan(account_request, true).map(|system_plan| vec![system_plan.instruction]),
+        InstructionSpec::AllocateSystemAccount(allocate_request) => build_allocate_system_account_plan(allocate_request, false).map(|system_plan| vec![system_plan.instruction]),
+        InstructionSpec::AllocateSystemAccountWithSeed(allocate_request) => build_allocate_system_account_plan(allocate_request, true).map(|system_plan| vec![system_plan.instruction]),
+        InstructionSpec::AssignSystemAccount(assign_request) => build_assign_system_account_plan(assign_request, false).map(|system_plan| vec![system_plan.instruction]),
+        InstructionSpec::AssignSystemAccountWithSeed(assign_request) => build_assign_system_account_plan(assign_request, true).map(|system_plan| vec![system_plan.instruction]),
+        InstructionSpec::Instruction(raw_instruction) => parse_raw_instruction(raw_instruction).map(|instruction| vec![instruction]),
+    }
+}
+
+fn spec_compute_budget(instruction_spec: &InstructionSpec) -> Option<&ComputeBudgetSpec> {
+    match instruction_spec {
+        InstructionSpec::CreateToken(token_creation_request) => Some(&token_creation_request.compute_budget),
+        InstructionSpec::MintToken(token_minting_request) => Some(&token_minting_request.compute_budget),
+        InstructionSpec::SendSol(transfer_request) => Some(&transfer_request.compute_budget),
+        InstructionSpec::SendToken(token_request) => Some(&token_request.compute_budget),
+        InstructionSpec::BurnToken(burn_request) => Some(&burn_request.compute_budget),
+        InstructionSpec::ApproveToken(approve_request) => Some(&approve_request.compute_budget),
+        InstructionSpec::RevokeToken(revoke_request) => Some(&revoke_request.compute_budget),
+        InstructionSpec::FreezeAccount(freeze_request) | InstructionSpec::ThawAccount(freeze_request) => Some(&freeze_request.compute_budget),
+        InstructionSpec::CloseAccount(close_request) => Some(&close_request.compute_budget),
+        InstructionSpec::SetAuthority(authority_request) => Some(&authority_request.compute_budget),
+        InstructionSpec::CreateMultisig(multisig_request) => Some(&multisig_request.compute_budget),
+        InstructionSpec::WrapSol(wrap_request) => Some(&wrap_request.compute_budget),
+        InstructionSpec::UnwrapSol(unwrap_request) => Some(&unwrap_request.compute_budget),
+        InstructionSpec::Memo(memo_request) => Some(&memo_request.compute_budget),
+        InstructionSpec::CreateNonceAccount(nonce_request) => Some(&nonce_request.compute_budget),
+        InstructionSpec::AdvanceNonceAccount(advance_request) => Some(&advance_request.compute_budget),
+        InstructionSpec::WithdrawNonceAccount(withdraw_request) => Some(&withdraw_request.compute_budget),
+        InstructionSpec::AuthorizeNonceAccount(authorize_request) => Some(&authorize_request.compute_budget),
+        InstructionSpec::CreateStakeAccount(stake_request) => Some(&stake_request.compute_budget),
+        InstructionSpec::DelegateStake(delegate_request) => Some(&delegate_request.compute_budget),
+        InstructionSpec::DeactivateStake(deactivate_request) => Some(&deactivate_request.compute_budget),
+        InstructionSpec::WithdrawStake(withdraw_request) => Some(&withdraw_request.compute_budget),
+        InstructionSpec::SplitStake(split_request) => Some(&split_request.compute_budget),
+        InstructionSpec::MergeStake(merge_request) => Some(&merge_request.compute_budget),
+        InstructionSpec::CreateSystemAccount(account_request) | InstructionSpec::CreateSystemAccountWithSeed(account_request) => Some(&account_request.compute_budget),
+        InstructionSpec::AllocateSystemAccount(allocate_request) | InstructionSpec::AllocateSystemAccountWithSeed(allocate_request) => Some(&allocate_request.compute_budget),
+        InstructionSpec::AssignSystemAccount(assign_request) | InstructionSpec::AssignSystemAccountWithSeed(assign_request) => Some(&assign_request.compute_budget),
+        InstructionSpec::Instruction(_) => None,
+    }
+}
+
+fn parse_lookup_table(lookup_table_spec: &AddressLookupTableSpec) -> Result<AddressLookupTableAccount, String> {
+    let table_address = match &lookup_table_spec.key {
+        Some(table_string) if !table_string.is_empty() => table_string,
+        _ => return Err("Every address lookup table needs a key".to_string()),
+    };
+
+    let table_addresses = match &lookup_table_spec.addresses {
+        Some(addresses) if !addresses.is_empty() && addresses.len() <= 256 => addresses,
+        Some(addresses) if addresses.len() > 256 => return Err("An address lookup table can hold at most 256 addresses".to_string()),
+        _ => return Err("Please provide the addresses stored in each lookup table".to_string()),
+    };
+
+    Ok(AddressLookupTableAccount {
+        key: parse_pubkey(table_address)?,
+        addresses: table_addresses.iter().map(|address| parse_pubkey(address)).collect::<Result<Vec<_>, _>>()?,
+    })
+}
+
+fn encode_transaction_data(
+    transaction_version: &str,
+    serialized_transaction: Vec<u8>,
+    message_hash: Hash,
+    required_signers: &[Pubkey],
+    address_table_lookups: Option<Vec<AddressTableLookupData>>,
+) -> Result<TransactionData, String> {
+    if serialized_transaction.len() > PACKET_DATA_SIZE {
+        return Err(format!("The transaction is {} bytes, which exceeds the {} byte packet limit", serialized_transaction.len(), PACKET_DATA_SIZE));
+    }
+
+    Ok(TransactionData {
+        version: transaction_version.to_string(),
+        transaction_base64: BASE64_STANDARD.encode(&serialized_transaction),
+        transaction_base58: bs58::encode(&serialized_transaction).into_string(),
+        message_hash: message_hash.to_string(),
+        required_signers: required_signers.iter().map(|signer_key| signer_key.to_string()).collect(),
+        size_bytes: serialized_transaction.len(),
+        address_table_lookups,
+        priority_fee_lamports: None,
+    })
+}
+
+fn compile_legacy_transaction(
+    fee_payer_public_key: &Pubkey,
+    transaction_instructions: &[Instruction],
+    recent_blockhash: &Hash,
+) -> Result<TransactionData, String> {
+    let transaction_message = Message::new_with_blockhash(transaction_instructions, Some(fee_payer_public_key), recent_blockhash);
+    let required_signers = transaction_message.account_keys[..transaction_message.header.num_required_signatures as usize].to_vec();
+    let message_hash = transaction_message.hash();
+    let unsigned_transaction = Transaction::new_unsigned(transaction_message);
+
+    let serialized_transaction = bincode::serialize(&unsigned_transaction)
+        .map_err(|_| "Unable to serialize the transaction".to_string())?;
+
+    encode_transaction_data("legacy", serialized_transaction, message_hash, &required_signers, None)
+}
+
+fn compile_v0_transaction(
+    fee_payer_public_key: &Pubkey,
+    transaction_instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<TransactionData, String> {
+    let compiled_message = v0::Message::try_compile(fee_payer_public_key, transaction_instructions, lookup_tables, recent_blockhash)
+        .map_err(|compile_error| format!("Unable to compile the v0 message: {}", compile_error))?;
+
+    let address_table_lookups = compiled_message
+        .address_table_lookups
+        .iter()
+        .map(|table_lookup| {
+            let lookup_table = lookup_tables
+                .iter()
+                .find(|table| table.key == table_lookup.account_key)
+                .expect("compiled lookups always reference a provided table");
+            let resolve_indexes = |indexes: &[u8]| -> Vec<String> {
+                indexes.iter().map(|index| lookup_table.addresses[*index as usize].to_string()).collect()
+            };
+            AddressTableLookupData {
+                table_key: table_lookup.account_key.to_string(),
+                writable_indexes: table_lookup.writable_indexes.clone(),
+                writable_addresses: resolve_indexes(&table_lookup.writable_indexes),
+                readonly_indexes: table_lookup.readonly_indexes.clone(),
+                readonly_addresses: resolve_indexes(&table_lookup.readonly_indexes),
+            }
+        })
+        .collect();
+
+    let required_signers = compiled_message.account_keys[..compiled_message.header.num_required_signatures as usize].to_vec();
+    let versioned_message = VersionedMessage::V0(compiled_message);
+    let message_hash = versioned_message.hash();
+    let unsigned_transaction = VersionedTransaction {
+        signatures: vec![Signature::default(); required_signers.len()],
+        message: versioned_message,
+    };
+
+    let serialized_transaction = bincode::serialize(&unsigned_transaction)
+        .map_err(|_| "Unable to serialize the transaction".to_string())?;
+
+    encode_transaction_data("v0", serialized_transaction, message_hash, &required_signers, Some(address_table_lookups))
+}
+
+// The runtime only treats a transaction as durable when advancing the nonce is its first instruction, ahead of the compute budget
+fn budget_transaction_instructions(
+    mut transaction_instructions: Vec<Instruction>,
+    nonce_advance_instruction: Option<Instruction>,
+    compute_budget: &ComputeBudgetSpec,
+) -> Result<(Vec<Instruction>, Option<u64>), String> {
+    let Some(advance_instruction) = nonce_advance_instruction else {
+        return prepend_compute_budget_instructions(transaction_instructions, compute_budget);
+    };
+
+    // The advance is budgeted with the rest so the default compute unit limit counts it
+    transaction_instructions.insert(0, advance_instruction);
+    let unbudgeted_instruction_count = transaction_instructions.len();
+    let (mut budgeted_instructions, priority_fee_lamports) = prepend_compute_budget_instructions(transaction_instructions, compute_budget)?;
+
+    let advance_instruction = budgeted_instructions.remove(budgeted_instructions.len() - unbudgeted_instruction_count);
+    budgeted_instructions.insert(0, advance_instruction);
+    Ok((budgeted_instructions, priority_fee_lamports))
+}
+
+pub async fn build_transaction(
+    Json(transaction_request): Json<BuildTransactionRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<TransactionData>>) {
+    let fee_payer_address = match &transaction_request.fee_payer {
+        Some(payer_address) if !payer_address.is_empty() => payer_address,
+        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("A fee payer address is required to build a transaction".to_string()))),
+    };
+
+    let durable_nonce_address = transaction_request.nonce_account.as_deref().filter(|nonce_address| !nonce_address.is_empty());
+    let recent_blockhash_string = match (&transaction_request.recent_blockhash, durable_nonce_address, &transaction_request.nonce) {
+        (Some(blockhash_string), None, None) if !blockhash_string.is_empty() => blockhash_string,
+        (Some(blockhash_string), Some(_), _) if !blockhash_string.is_empty() => {
+            return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Provide either a recent blockhash or a durable nonce, not both".to_string())));
+        }
+        (_, Some(_), Some(nonce_value)) if !nonce_value.is_empty() => nonce_value,
+        (_, Some(_), _) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Please provide the nonce value currently stored in the nonce account".to_string()))),
+        (_, None, Some(_)) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("A nonce value can only be used together with its nonce account".to_string()))),
+        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Please provide a recent blockhash for the transaction, or a nonce account and its nonce value".to_string()))),
+    };
+
+    let instruction_specs = match &transaction_request.instructions {
+        Some(specs) if !specs.is_empty() => specs,
+        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("At least one instruction is required to build a transaction".to_string()))),
+    };
+
+    let use_versioned_message = match transaction_request.version.as_deref() {
+        None | Some("legacy") => false,
+        Some("v0") => true,
+        Some(_) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Transaction version must be either \"legacy\" or \"v0\"".to_string()))),
+    };
+
+    let lookup_table_specs = transaction_request.address_lookup_tables.as_deref().unwrap_or_default();
+    if !use_versioned_message && !lookup_table_specs.is_empty() {
+        return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Address lookup tables can only be used with v0 transactions".to_string())));
+    }
+
+    let fee_payer_public_key = match parse_pubkey(fee_payer_address) {
+        Ok(valid_pubkey) => valid_pubkey,
+        Err(parsing_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(parsing_error))),
+    };
+
+    let recent_blockhash = match parse_blockhash(recent_blockhash_string) {
+        Ok(valid_blockhash) => valid_blockhash,
+        Err(parsing_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(parsing_error))),
+    };
+
+    let nonce_advance_instruction = match durable_nonce_address {
+        Some(nonce_address) => {
+            let fee_payer_string = fee_payer_public_key.to_string();
+            let nonce_authority_address = transaction_request.nonce_authority.as_deref().unwrap_or(&fee_payer_string);
+            match parse_nonce_account_and_authority(Some(nonce_address), Some(nonce_authority_address)) {
+                Ok((nonce_account_public_key, nonce_authority_public_key)) => {
+                    Some(system_instruction::advance_nonce_account(&nonce_account_public_key, &nonce_authority_public_key))
+                }
+                Err(validation_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(validation_error))),
+            }
+        }
+        None => None,
+    };
+
+    let lookup_tables = match lookup_table_specs.iter().map(parse_lookup_table).collect::<Result<Vec<_>, _>>() {
+        Ok(parsed_tables) => parsed_tables,
+        Err(parsing_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(parsing_error))),
+    };
+
+    if lookup_tables.iter().enumerate().any(|(table_index, table)| lookup_tables[..table_index].iter().any(|earlier_table| earlier_table.key == table.key)) {
+        return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Each address lookup table may only be provided once".to_string())));
+    }
+
+    let mut transaction_instructions = Vec::with_capacity(instruction_specs.len());
+    for (instruction_index, instruction_spec) in instruction_specs.iter().enumerate() {
+        let sets_compute_budget = spec_compute_budget(instruction_spec).is_some_and(|compute_budget| {
+            compute_budget.compute_unit_limit.is_some() || compute_budget.compute_unit_price_micro_lamports.is_some()
+        });
+        if sets_compute_budget {
+            return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(format!("Instruction {}: Set the compute budget on the transaction rather than on individual instructions", instruction_index))));
+        }
+
+        match build_spec_instructions(instruction_spec) {
+            Ok(created_instructions) => transaction_instructions.extend(created_instructions),
+            Err(validation_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(format!("Instruction {}: {}", instruction_index, validation_error)))),
+        }
+    }
+
+    let (transaction_instructions, priority_fee_lamports) =
+        match budget_transaction_instructions(transaction_instructions, nonce_advance_instruction, &transaction_request.compute_budget) {
+            Ok(budgeted_instructions) => budgeted_instructions,
+            Err(validation_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(validation_error))),
+        };
+
+    let compiled_transaction = if use_versioned_message {
+        compile_v0_transaction(&fee_payer_public_key, &transaction_instructions, &lookup_tables, recent_blockhash)
+    } else {
+        compile_legacy_transaction(&fee_payer_public_key, &transaction_instructions, &recent_blockhash)
+    };
+
+    match compiled_transaction {
+        Ok(transaction_data) => (StatusCode::OK, ResponseJson(ApiResponse::success(TransactionData {
+            priority_fee_lamports,
+            ..transaction_data
+        }))),
+        Err(build_error) => (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(build_error))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nonce_and_transfer_instructions() -> (Instruction, Vec<Instruction>) {
+        let (nonce_account, nonce_authority, recipient) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        (
+            system_instruction::advance_nonce_account(&nonce_account, &nonce_authority),
+            vec![system_instruction::transfer(&nonce_authority, &recipient, 1_000)],
+        )
+    }
+
+    fn program_ids(instructions: &[Instruction]) -> Vec<Pubkey> {
+        instructions.iter().map(|instruction| instruction.program_id).collect()
+    }
+
+    #[test]
+    fn nonce_advance_stays_ahead_of_the_compute_budget() {
+        let (advance_instruction, transfer_instructions) = nonce_and_transfer_instructions();
+        let compute_budget = ComputeBudgetSpec { compute_unit_limit: Some(300_000), compute_unit_price_micro_lamports: Some(10) };
+
+        let (budgeted_instructions, priority_fee_lamports) =
+            budget_transaction_instructions(transfer_instructions.clone(), Some(advance_instruction.clone()), &compute_budget).unwrap();
+
+        assert_eq!(budgeted_instructions.len(), 4);
+        assert_eq!(budgeted_instructions[0], advance_instruction);
+        assert_eq!(program_ids(&budgeted_instructions[1..3]), vec![solana_sdk::compute_budget::id(); 2]);
+        assert_eq!(budgeted_instructions[3], transfer_instructions[0]);
+        assert_eq!(priority_fee_lamports, Some(3));
+    }
+
+    #[test]
+    fn nonce_advance_counts_toward_the_default_compute_unit_limit() {
+        let (advance_instruction, transfer_instructions) = nonce_and_transfer_instructions();
+        let compute_budget = ComputeBudgetSpec { compute_unit_limit: None, compute_unit_price_micro_lamports: Some(1_000_000) };
+
+        let (budgeted_instructions, priority_fee_lamports) =
+            budget_transaction_instructions(transfer_instructions, Some(advance_instruction.clone()), &compute_budget).unwrap();
+
+        assert_eq!(budgeted_instructions[0], advance_instruction);
+        assert_eq!(budgeted_instructions.len(), 3);
+        assert_eq!(priority_fee_lamports, Some(400_000));
+    }
+
+    #[test]
+    fn compute_budget_leads_without_a_nonce() {
+        let (_, transfer_instructions) = nonce_and_transfer_instructions();
+        let compute_budget = ComputeBudgetSpec { compute_unit_limit: Some(1_000), compute_unit_price_micro_lamports: None };
+
+        let (budgeted_instructions, priority_fee_lamports) = budget_transaction_instructions(transfer_instructions.clone(), None, &compute_budget).unwrap();
+
+        assert_eq!(program_ids(&budgeted_instructions), vec![solana_sdk::compute_budget::id(), solana_program::system_program::id()]);
+        assert_eq!(budgeted_instructions[1], transfer_instructions[0]);
+        assert_eq!(priority_fee_lamports, Some(0));
+    }
+
+    #[test]
+    fn instructions_pass_through_without_a_nonce_or_budget() {
+        let (_, transfer_instructions) = nonce_and_transfer_instructions();
+        let compute_budget = ComputeBudgetSpec { compute_unit_limit: None, compute_unit_price_micro_lamports: None };
+
+        let (budgeted_instructions, priority_fee_lamports) = budget_transaction_instructions(transfer_instructions.clone(), None, &compute_budget).unwrap();
+
+        assert_eq!(budgeted_instructions, transfer_instructions);
+        assert_eq!(priority_fee_lamports, None);
+    }
+}
@@ -1,141 +1,233 @@
 use axum::{extract::Json, http::StatusCode, response::Json as ResponseJson};
-use solana_program::system_instruction;
-use spl_token::instruction as token_instruction;
-
-use crate::models::{ApiResponse, SendSolRequest, SendTokenRequest, SolTransferData, TokenTransferData, TokenAccountInfo};
-use crate::utils::{parse_pubkey};
-
-pub async fn handle_solana_transfer_request(
-    Json(transfer_request): Json<SendSolRequest>,
-) -> (StatusCode, ResponseJson<ApiResponse<SolTransferData>>) {
+use solana_program::{instruction::Instruction, pubkey::Pubkey, system_instruction};
+use spl_token::instruction::TokenInstruction;
+use spl_token_2022::instruction as token_instruction;
+
+use crate::handlers::build_memo_instruction;
+use crate::models::{
+    ApiResponse, ComputeBudgetSpec, InstructionData, InstructionOutput, SendSolRequest, SendTokenRequest, SolTransferData,
+};
+use crate::utils::{
+    instruction_to_response, instructions_to_output, parse_multisig_signers, parse_optional_pubkey, parse_pubkey, parse_token_decimals,
+    parse_token_program, prepend_compute_budget_instructions, result_to_response, token_amount_to_base_units,
+};
+
+pub fn build_sol_transfer_instructions(transfer_request: &SendSolRequest) -> Result<Vec<Instruction>, String> {
     let sender_wallet = match &transfer_request.from {
         Some(wallet_address) if !wallet_address.is_empty() => wallet_address,
-        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Please provide a valid sender wallet address".to_string()))),
+        _ => return Err("Please provide a valid sender wallet address".to_string()),
     };
     
     let recipient_wallet = match &transfer_request.to {
         Some(wallet_address) if !wallet_address.is_empty() => wallet_address,
-        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Please provide a valid recipient wallet address".to_string()))),
+        _ => return Err("Please provide a valid recipient wallet address".to_string()),
     };
     
     let transfer_amount_in_lamports = match transfer_request.lamports {
-        Some(0) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Amount must be greater than 0".to_string()))),
-        Some(amount) if amount > 100_000_000_000_000 => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("The transfer amount exceeds the maximum allowed limit".to_string()))),
+        Some(0) => return Err("Amount must be greater than 0".to_string()),
+        Some(amount) if amount > 100_000_000_000_000 => return Err("The transfer amount exceeds the maximum allowed limit".to_string()),
         Some(amount) => amount,
-        None => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Please specify the amount you want to transfer".to_string()))),
-    };
-    let sender_public_key = match parse_pubkey(sender_wallet) {
-        Ok(parsed_key) => parsed_key,
-        Err(_) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Invalid sender public key".to_string()))),
-    };
-        
-    let recipient_public_key = match parse_pubkey(recipient_wallet) {
-        Ok(parsed_key) => parsed_key,
-        Err(validation_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(validation_error))),
+        None => return Err("Please specify the amount you want to transfer".to_string()),
     };
+    let sender_public_key = parse_pubkey(sender_wallet).map_err(|_| "Invalid sender public key".to_string())?;
+    let recipient_public_key = parse_pubkey(recipient_wallet)?;
 
     if sender_public_key == recipient_public_key {
-        return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Cannot transfer to the same address".to_string())));
+        return Err("Cannot transfer to the same address".to_string());
     }
 
     if sender_public_key == solana_program::system_program::id() || recipient_public_key == solana_program::system_program::id() {
-        return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Transfers involving the system program are not permitted".to_string())));
+        return Err("Transfers involving the system program are not permitted".to_string());
     }
 
-    let blockchain_instruction = system_instruction::transfer(&sender_public_key, &recipient_public_key, transfer_amount_in_lamports);
+    let mut transfer_instructions = vec![system_instruction::transfer(&sender_public_key, &recipient_public_key, transfer_amount_in_lamports)];
+    if let Some(memo_text) = &transfer_request.memo {
+        transfer_instructions.push(build_memo_instruction(memo_text, &[&sender_public_key])?);
+    }
+
+    Ok(transfer_instructions)
+}
 
-    let transfer_response = SolTransferData {
-        program_id: blockchain_instruction.program_id.to_string(),
-        accounts: blockchain_instruction.accounts.iter().map(|account| account.pubkey.to_string()).collect(),
-        instruction_data: bs58::encode(&blockchain_instruction.data).into_string(),
+// The transfer itself always fills the top-level fields; a memo or compute budget adds the full ordered `instructions` list beside it
+fn sol_transfer_to_response(transfer_instructions: Vec<Instruction>, compute_budget: &ComputeBudgetSpec) -> Result<SolTransferData, String> {
+    let transfer_instruction = &transfer_instructions[0];
+    let mut transfer_data = SolTransferData {
+        program_id: transfer_instruction.program_id.to_string(),
+        accounts: transfer_instruction.accounts.iter().map(|account| account.pubkey.to_string()).collect(),
+        instruction_data: bs58::encode(&transfer_instruction.data).into_string(),
+        instructions: None,
+        priority_fee_lamports: None,
     };
 
-    (StatusCode::OK, ResponseJson(ApiResponse::success(transfer_response)))
+    let (budgeted_instructions, priority_fee_lamports) = prepend_compute_budget_instructions(transfer_instructions, compute_budget)?;
+    if budgeted_instructions.len() > 1 {
+        transfer_data.instructions = Some(budgeted_instructions.into_iter().map(instruction_to_response).collect());
+        transfer_data.priority_fee_lamports = priority_fee_lamports;
+    }
+
+    Ok(transfer_data)
 }
 
-pub async fn handle_token_transfer_between_users(
-    Json(token_request): Json<SendTokenRequest>,
-) -> (StatusCode, ResponseJson<ApiResponse<TokenTransferData>>) {
+pub async fn handle_solana_transfer_request(
+    Json(transfer_request): Json<SendSolRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<SolTransferData>>) {
+    result_to_response(
+        build_sol_transfer_instructions(&transfer_request)
+            .and_then(|transfer_instructions| sol_transfer_to_response(transfer_instructions, &transfer_request.compute_budget)),
+    )
+}
+
+pub fn build_token_transfer_instructions(token_request: &SendTokenRequest) -> Result<Vec<Instruction>, String> {
     let receiving_user_address = match &token_request.destination {
         Some(address_string) if !address_string.is_empty() => address_string,
-        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Destination wallet address is required for this operation".to_string()))),
+        _ => return Err("Destination wallet address is required for this operation".to_string()),
     };
     
     let token_mint_address = match &token_request.mint {
         Some(mint_string) if !mint_string.is_empty() => mint_string,
-        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Token mint address must be specified".to_string()))),
+        _ => return Err("Token mint address must be specified".to_string()),
     };
     
     let current_token_owner = match &token_request.owner {
         Some(owner_string) if !owner_string.is_empty() => owner_string,
-        _ => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Current token owner address is needed".to_string()))),
+        _ => return Err("Current token owner address is needed".to_string()),
     };
     
-    let token_transfer_amount = match token_request.amount {
-        Some(0) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Amount must be greater than 0".to_string()))),
-        Some(requested_amount) if requested_amount > u64::MAX / 2 => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("The requested transfer amount is unreasonably large".to_string()))),
+    let token_decimal_places = parse_token_decimals(token_request.decimals)?;
+    let requested_transfer_amount = token_request
+        .amount
+        .as_ref()
+        .map(|token_amount| token_amount_to_base_units(token_amount, token_decimal_places))
+        .transpose()?;
+
+    let token_transfer_amount = match requested_transfer_amount {
+        Some(0) => return Err("Amount must be greater than 0".to_string()),
+        Some(requested_amount) if requested_amount > u64::MAX / 2 => return Err("The requested transfer amount is unreasonably large".to_string()),
         Some(valid_amount) => valid_amount,
-        None => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Please specify how many tokens to transfer".to_string()))),
+        None => return Err("Please specify how many tokens to transfer".to_string()),
     };
 
-    let token_mint_public_key = match parse_pubkey(token_mint_address) {
-        Ok(parsed_mint_key) => parsed_mint_key,
-        Err(parsing_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(parsing_error))),
-    };
-    
-    let owner_public_key = match parse_pubkey(current_token_owner) {
-        Ok(parsed_owner_key) => parsed_owner_key,
-        Err(parsing_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(parsing_error))),
-    };
-    
-    let destination_public_key = match parse_pubkey(receiving_user_address) {
-        Ok(parsed_destination_key) => parsed_destination_key,
-        Err(parsing_error) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(parsing_error))),
-    };
+    let token_program_id = parse_token_program(token_request.program.as_deref())?;
+    let multisig_signers = parse_multisig_signers(token_request.multisig_signers.as_deref())?;
+    let multisig_signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    let token_mint_public_key = parse_pubkey(token_mint_address)?;
+    let owner_public_key = parse_pubkey(current_token_owner)?;
+    let destination_public_key = parse_pubkey(receiving_user_address)?;
 
     if owner_public_key == destination_public_key {
-        return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Cannot transfer to the same address".to_string())));
+        return Err("Cannot transfer to the same address".to_string());
     }
 
     if owner_public_key == solana_program::system_program::id() || destination_public_key == solana_program::system_program::id() {
-        return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Token transfers involving the system program are not allowed".to_string())));
+        return Err("Token transfers involving the system program are not allowed".to_string());
     }
 
-    let sender_token_account = spl_associated_token_account::get_associated_token_address(&owner_public_key, &token_mint_public_key);
-    let receiver_token_account = spl_associated_token_account::get_associated_token_address(&destination_public_key, &token_mint_public_key);
-
-    let token_transfer_instruction = match token_instruction::transfer(
-        &spl_token::id(),
-        &sender_token_account,
-        &receiver_token_account,
-        &owner_public_key,
-        &[],
-        token_transfer_amount,
-    ) {
-        Ok(created_instruction) => created_instruction,
-        Err(_) => return (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error("Unable to create the token transfer instruction".to_string()))),
+    let sender_token_account = spl_associated_token_account::get_associated_token_address_with_program_id(&owner_public_key, &token_mint_public_key, &token_program_id);
+    let receiver_token_account = spl_associated_token_account::get_associated_token_address_with_program_id(&destination_public_key, &token_mint_public_key, &token_program_id);
+
+    let token_transfer_instruction = match token_decimal_places {
+        Some(decimal_count) => token_instruction::transfer_checked(
+            &token_program_id,
+            &sender_token_account,
+            &token_mint_public_key,
+            &receiver_token_account,
+            &owner_public_key,
+            &multisig_signer_refs,
+            token_transfer_amount,
+            decimal_count,
+        ),
+        // Token-2022 deprecates the unchecked transfer, but still accepts it for mints without a transfer fee
+        #[allow(deprecated)]
+        None => token_instruction::transfer(
+            &token_program_id,
+            &sender_token_account,
+            &receiver_token_account,
+            &owner_public_key,
+            &multisig_signer_refs,
+            token_transfer_amount,
+        ),
     };
 
-    let account_details = vec![
-        TokenAccountInfo {
-            pubkey: owner_public_key.to_string(),
-            is_signer: false,
-        },
-        TokenAccountInfo {
-            pubkey: receiver_token_account.to_string(),
-            is_signer: false,
-        },
-        TokenAccountInfo {
-            pubkey: owner_public_key.to_string(),
-            is_signer: true,
-        },
-    ];
-
-    let token_transfer_response = TokenTransferData {
-        program_id: token_transfer_instruction.program_id.to_string(),
-        accounts: account_details,
-        instruction_data: bs58::encode(&token_transfer_instruction.data).into_string(),
+    let token_transfer_instruction = token_transfer_instruction.map_err(|_| "Unable to create the token transfer instruction".to_string())?;
+
+    let mut transfer_instructions = Vec::new();
+    if token_request.create_destination_ata.unwrap_or(false) {
+        let payer_public_key = match parse_optional_pubkey(token_request.payer.as_deref())? {
+            Some(payer_public_key) => payer_public_key,
+            None if !multisig_signers.is_empty() => {
+                return Err("A multisig owner cannot sign to pay for the destination token account, so please provide a payer".to_string());
+            }
+            None => owner_public_key,
+        };
+        if payer_public_key == solana_program::system_program::id() {
+            return Err("The system program cannot pay for the destination token account".to_string());
+        }
+
+        transfer_instructions.push(spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            &payer_public_key,
+            &destination_public_key,
+            &token_mint_public_key,
+            &token_program_id,
+        ));
+    }
+    transfer_instructions.push(token_transfer_instruction);
+
+    if let Some(memo_text) = &token_request.memo {
+        // A multisig owner cannot sign itself, so its signers sign the memo instead
+        let memo_signer_refs = if multisig_signer_refs.is_empty() { vec![&owner_public_key] } else { multisig_signer_refs };
+        transfer_instructions.push(build_memo_instruction(memo_text, &memo_signer_refs)?);
+    }
+
+    Ok(transfer_instructions)
+}
+
+fn token_transfer_to_response(blockchain_instruction: Instruction) -> InstructionData {
+    let is_token_program = blockchain_instruction.program_id == spl_token::id() || blockchain_instruction.program_id == spl_token_2022::id();
+    let account_roles: &[&str] = match TokenInstruction::unpack(&blockchain_instruction.data) {
+        Ok(TokenInstruction::Transfer { .. }) if is_token_program => &["source", "destination", "owner"],
+        Ok(TokenInstruction::TransferChecked { .. }) if is_token_program => &["source", "mint", "destination", "owner"],
+        _ => return instruction_to_response(blockchain_instruction),
     };
 
-    (StatusCode::OK, ResponseJson(ApiResponse::success(token_transfer_response)))
-}
\ No newline at end of file
+    let mut instruction_response = instruction_to_response(blockchain_instruction);
+    for (account_index, account_information) in instruction_response.accounts.iter_mut().enumerate() {
+        account_information.role = Some(account_roles.get(account_index).copied().unwrap_or("multisig_signer").to_string());
+    }
+
+    instruction_response
+}
+
+pub async fn handle_token_transfer_between_users(
+    Json(token_request): Json<SendTokenRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    result_to_response(build_token_transfer_instructions(&token_request).and_then(|token_transfer_instructions| {
+        instructions_to_output(token_transfer_instructions, &token_request.compute_budget, token_transfer_to_response, token_transfer_to_response)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn multisig_owner_needs_an_explicit_payer_for_the_destination_ata() {
+        let payer = Pubkey::new_unique();
+        let mut multisig_transfer_json = json!({
+            "destination": Pubkey::new_unique().to_string(),
+            "mint": Pubkey::new_unique().to_string(),
+            "owner": Pubkey::new_unique().to_string(),
+            "amount": 5,
+            "createDestinationAta": true,
+            "multisigSigners": [Pubkey::new_unique().to_string()],
+        });
+        let multisig_transfer_request: SendTokenRequest = serde_json::from_value(multisig_transfer_json.clone()).unwrap();
+        let transfer_error = build_token_transfer_instructions(&multisig_transfer_request).err().unwrap();
+        assert!(transfer_error.contains("please provide a payer"));
+
+        multisig_transfer_json["payer"] = json!(payer.to_string());
+        let multisig_transfer_request: SendTokenRequest = serde_json::from_value(multisig_transfer_json).unwrap();
+        let transfer_instructions = build_token_transfer_instructions(&multisig_transfer_request).unwrap();
+        assert_eq!(transfer_instructions[0].accounts[0].pubkey, payer);
+    }
+}
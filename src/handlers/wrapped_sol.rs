@@ -0,0 +1,96 @@
+use axum::{extract::Json, http::StatusCode, response::Json as ResponseJson};
+use solana_program::{instruction::Instruction, pubkey::Pubkey, system_instruction};
+use spl_token_2022::instruction as token_instruction;
+
+use crate::models::{ApiResponse, InstructionBundleData, InstructionData, InstructionOutput, UnwrapSolRequest, WrapSolRequest};
+use crate::utils::{
+    instruction_to_response, instructions_result_to_response, instructions_to_bundle, parse_multisig_signers,
+    parse_optional_pubkey, parse_pubkey, parse_token_program, resolve_token_account, result_to_response,
+};
+
+fn native_mint_for_program(token_program_id: &Pubkey) -> Pubkey {
+    if *token_program_id == spl_token_2022::id() {
+        spl_token_2022::native_mint::id()
+    } else {
+        spl_token::native_mint::id()
+    }
+}
+
+pub fn build_wrap_sol_instructions(wrap_request: &WrapSolRequest) -> Result<Vec<Instruction>, String> {
+    let wallet_owner_address = match &wrap_request.owner {
+        Some(owner_address) if !owner_address.is_empty() => owner_address,
+        _ => return Err("Please provide the wallet address whose SOL you want to wrap".to_string()),
+    };
+
+    let lamports_to_wrap = match wrap_request.lamports {
+        Some(0) => return Err("Amount must be greater than 0".to_string()),
+        Some(amount) if amount > 100_000_000_000_000 => return Err("The wrap amount exceeds the maximum allowed limit".to_string()),
+        Some(amount) => amount,
+        None => return Err("Please specify how many lamports you want to wrap".to_string()),
+    };
+
+    let token_program_id = parse_token_program(wrap_request.program.as_deref())?;
+    let owner_public_key = parse_pubkey(wallet_owner_address)?;
+    let payer_public_key = parse_optional_pubkey(wrap_request.payer.as_deref())?.unwrap_or(owner_public_key);
+
+    if owner_public_key == solana_program::system_program::id() || payer_public_key == solana_program::system_program::id() {
+        return Err("Wrapping SOL for or from the system program is not permitted".to_string());
+    }
+
+    let native_mint = native_mint_for_program(&token_program_id);
+    let wrapped_sol_account = spl_associated_token_account::get_associated_token_address_with_program_id(&owner_public_key, &native_mint, &token_program_id);
+
+    let sync_instruction = token_instruction::sync_native(&token_program_id, &wrapped_sol_account)
+        .map_err(|_| "Unable to create the sync native instruction".to_string())?;
+
+    Ok(vec![
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            &payer_public_key,
+            &owner_public_key,
+            &native_mint,
+            &token_program_id,
+        ),
+        system_instruction::transfer(&owner_public_key, &wrapped_sol_account, lamports_to_wrap),
+        sync_instruction,
+    ])
+}
+
+pub async fn wrap_sol(
+    Json(wrap_request): Json<WrapSolRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionBundleData>>) {
+    let wrap_bundle = build_wrap_sol_instructions(&wrap_request).and_then(|wrap_instructions| {
+        instructions_to_bundle(wrap_instructions, &wrap_request.compute_budget, instruction_to_response)
+    });
+
+    result_to_response(wrap_bundle)
+}
+
+pub fn build_unwrap_sol_instruction(unwrap_request: &UnwrapSolRequest) -> Result<Instruction, String> {
+    let wallet_owner_address = match &unwrap_request.owner {
+        Some(owner_address) if !owner_address.is_empty() => owner_address,
+        _ => return Err("Please provide the wallet address that owns the wrapped SOL".to_string()),
+    };
+
+    let token_program_id = parse_token_program(unwrap_request.program.as_deref())?;
+    let owner_public_key = parse_pubkey(wallet_owner_address)?;
+    let lamport_destination = parse_optional_pubkey(unwrap_request.destination.as_deref())?.unwrap_or(owner_public_key);
+
+    if owner_public_key == solana_program::system_program::id() || lamport_destination == solana_program::system_program::id() {
+        return Err("Unwrapping SOL for or to the system program is not permitted".to_string());
+    }
+
+    let multisig_signers = parse_multisig_signers(unwrap_request.multisig_signers.as_deref())?;
+    let multisig_signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+
+    let native_mint = native_mint_for_program(&token_program_id);
+    let wrapped_sol_account = resolve_token_account(unwrap_request.account.as_deref(), &owner_public_key, Some(&native_mint), &token_program_id)?;
+
+    token_instruction::close_account(&token_program_id, &wrapped_sol_account, &lamport_destination, &owner_public_key, &multisig_signer_refs)
+        .map_err(|_| "Unable to create the unwrap instruction".to_string())
+}
+
+pub async fn unwrap_sol(
+    Json(unwrap_request): Json<UnwrapSolRequest>,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    instructions_result_to_response(build_unwrap_sol_instruction(&unwrap_request).map(|instruction| vec![instruction]), &unwrap_request.compute_budget)
+}
@@ -11,12 +11,46 @@ use handlers::*;
 async fn main() {
     let app = Router::new()
         .route("/keypair", post(generate_keypair))
+        .route("/keypair/recover", post(recover_keypair))
+        .route("/keypair/grind", post(grind_keypair))
+        .route("/address/pda", post(derive_program_address))
+        .route("/address/inspect", post(inspect_address))
         .route("/token/create", post(create_token))
         .route("/token/mint", post(mint_token))
+        .route("/token/burn", post(burn_token))
+        .route("/token/approve", post(approve_token))
+        .route("/token/revoke", post(revoke_token))
+        .route("/token/freeze", post(freeze_token_account))
+        .route("/token/thaw", post(thaw_token_account))
+        .route("/token/close", post(close_token_account))
+        .route("/token/set-authority", post(set_token_authority))
+        .route("/token/multisig/create", post(create_token_multisig))
+        .route("/token/wrap-sol", post(wrap_sol))
+        .route("/token/unwrap-sol", post(unwrap_sol))
+        .route("/memo", post(create_memo))
         .route("/message/sign", post(sign_message))
         .route("/message/verify", post(verify_message))
+        .route("/nonce/create", post(create_nonce_account))
+        .route("/nonce/advance", post(advance_nonce_account))
+        .route("/nonce/withdraw", post(withdraw_nonce_account))
+        .route("/nonce/authorize", post(authorize_nonce_account))
+        .route("/stake/create", post(create_stake_account))
+        .route("/stake/delegate", post(delegate_stake))
+        .route("/stake/deactivate", post(deactivate_stake))
+        .route("/stake/withdraw", post(withdraw_stake))
+        .route("/stake/split", post(split_stake))
+        .route("/stake/merge", post(merge_stake))
+        .route("/system/create-account", post(create_system_account))
+        .route("/system/create-account-with-seed", post(create_system_account_with_seed))
+        .route("/system/allocate", post(allocate_system_account))
+        .route("/system/allocate-with-seed", post(allocate_system_account_with_seed))
+        .route("/system/assign", post(assign_system_account))
+        .route("/system/assign-with-seed", post(assign_system_account_with_seed))
         .route("/send/sol", post(handle_solana_transfer_request))
+        .route("/send/sol/batch", post(send_sol_batch))
         .route("/send/token", post(handle_token_transfer_between_users))
+        .route("/send/token/batch", post(send_token_batch))
+        .route("/transaction/build", post(build_transaction))
         .layer(CorsLayer::permissive());
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:8084")
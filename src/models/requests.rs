@@ -1,4 +1,53 @@
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Keeps an explicit `null` distinct from a missing field: absent is `None`, `null` is `Some(None)`.
+fn deserialize_nullable_field<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum SecretKeyFormat {
+    #[default]
+    Base58,
+    ByteArray,
+    Hex,
+}
+
+#[derive(Deserialize, Default)]
+pub struct GenerateKeypairRequest {
+    pub format: Option<SecretKeyFormat>,
+    pub mnemonic: Option<bool>,
+    pub words: Option<usize>,
+    pub passphrase: Option<String>,
+    #[serde(rename = "accountIndex")]
+    pub account_index: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct RecoverKeypairRequest {
+    pub format: Option<SecretKeyFormat>,
+    pub mnemonic: Option<String>,
+    pub passphrase: Option<String>,
+    #[serde(rename = "accountIndex")]
+    pub account_index: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct GrindKeypairRequest {
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    #[serde(rename = "caseInsensitive")]
+    pub case_insensitive: Option<bool>,
+    #[serde(rename = "timeoutSeconds")]
+    pub timeout_seconds: Option<u64>,
+    #[serde(rename = "maxAttempts")]
+    pub max_attempts: Option<u64>,
+}
 
 #[derive(Deserialize)]
 pub struct CreateTokenRequest {
@@ -6,6 +55,81 @@ pub struct CreateTokenRequest {
     pub mint_authority: Option<String>,
     pub mint: Option<String>,
     pub decimals: Option<u8>,
+    #[serde(rename = "freezeAuthority", default, deserialize_with = "deserialize_nullable_field")]
+    pub freeze_authority: Option<Option<String>>,
+    pub program: Option<String>,
+    pub extensions: Option<Vec<MintExtensionSpec>>,
+    pub payer: Option<String>,
+    #[serde(rename = "createMintAccount")]
+    pub create_mint_account: Option<bool>,
+    #[serde(rename = "rentSchedule")]
+    pub rent_schedule: Option<RentScheduleSpec>,
+    #[serde(rename = "createAuthorityAta")]
+    pub create_authority_ata: Option<bool>,
+    #[serde(rename = "initialSupply")]
+    pub initial_supply: Option<u64>,
+    #[serde(rename = "multisigSigners")]
+    pub multisig_signers: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct RentScheduleSpec {
+    #[serde(rename = "lamportsPerByteYear")]
+    pub lamports_per_byte_year: Option<u64>,
+    #[serde(rename = "exemptionThreshold")]
+    pub exemption_threshold: Option<f64>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum MintExtensionSpec {
+    TransferFeeConfig {
+        transfer_fee_config_authority: Option<String>,
+        withdraw_withheld_authority: Option<String>,
+        transfer_fee_basis_points: Option<u16>,
+        maximum_fee: Option<u64>,
+    },
+    MetadataPointer {
+        authority: Option<String>,
+        metadata_address: Option<String>,
+    },
+    TokenMetadata {
+        name: Option<String>,
+        symbol: Option<String>,
+        uri: Option<String>,
+        update_authority: Option<String>,
+    },
+    InterestBearingConfig {
+        rate_authority: Option<String>,
+        rate: Option<i16>,
+    },
+    NonTransferable,
+    PermanentDelegate {
+        delegate: Option<String>,
+    },
+    MintCloseAuthority {
+        close_authority: Option<String>,
+    },
+    DefaultAccountState {
+        state: Option<String>,
+    },
+}
+
+#[derive(Deserialize, Default)]
+pub struct ComputeBudgetSpec {
+    #[serde(rename = "computeUnitLimit")]
+    pub compute_unit_limit: Option<u32>,
+    #[serde(rename = "computeUnitPriceMicroLamports")]
+    pub compute_unit_price_micro_lamports: Option<u64>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(untagged)]
+pub enum TokenAmount {
+    BaseUnits(u64),
+    Decimal(String),
 }
 
 #[derive(Deserialize)]
@@ -13,13 +137,332 @@ pub struct MintTokenRequest {
     pub mint: Option<String>,
     pub destination: Option<String>,
     pub authority: Option<String>,
-    pub amount: Option<u64>,
+    pub amount: Option<TokenAmount>,
+    pub decimals: Option<u8>,
+    pub program: Option<String>,
+    #[serde(rename = "multisigSigners")]
+    pub multisig_signers: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct BurnTokenRequest {
+    pub mint: Option<String>,
+    pub owner: Option<String>,
+    pub account: Option<String>,
+    pub amount: Option<TokenAmount>,
+    pub decimals: Option<u8>,
+    pub program: Option<String>,
+    #[serde(rename = "multisigSigners")]
+    pub multisig_signers: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct ApproveTokenRequest {
+    pub mint: Option<String>,
+    pub owner: Option<String>,
+    pub account: Option<String>,
+    pub delegate: Option<String>,
+    pub amount: Option<TokenAmount>,
+    pub decimals: Option<u8>,
+    pub program: Option<String>,
+    #[serde(rename = "multisigSigners")]
+    pub multisig_signers: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct RevokeTokenRequest {
+    pub mint: Option<String>,
+    pub owner: Option<String>,
+    pub account: Option<String>,
+    pub program: Option<String>,
+    #[serde(rename = "multisigSigners")]
+    pub multisig_signers: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct FreezeAccountRequest {
+    pub mint: Option<String>,
+    pub authority: Option<String>,
+    pub owner: Option<String>,
+    pub account: Option<String>,
+    pub program: Option<String>,
+    #[serde(rename = "multisigSigners")]
+    pub multisig_signers: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct CloseAccountRequest {
+    pub mint: Option<String>,
+    pub owner: Option<String>,
+    pub account: Option<String>,
+    pub destination: Option<String>,
+    pub program: Option<String>,
+    #[serde(rename = "multisigSigners")]
+    pub multisig_signers: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct SetAuthorityRequest {
+    pub account: Option<String>,
+    #[serde(rename = "accountType")]
+    pub account_type: Option<String>,
+    #[serde(rename = "authorityType")]
+    pub authority_type: Option<String>,
+    #[serde(rename = "currentAuthority")]
+    pub current_authority: Option<String>,
+    #[serde(rename = "newAuthority", default, deserialize_with = "deserialize_nullable_field")]
+    pub new_authority: Option<Option<String>>,
+    pub program: Option<String>,
+    #[serde(rename = "multisigSigners")]
+    pub multisig_signers: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct CreateMultisigRequest {
+    pub multisig: Option<String>,
+    pub payer: Option<String>,
+    pub signers: Option<Vec<String>>,
+    pub threshold: Option<u8>,
+    pub program: Option<String>,
+    #[serde(rename = "rentSchedule")]
+    pub rent_schedule: Option<RentScheduleSpec>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct WrapSolRequest {
+    pub owner: Option<String>,
+    pub lamports: Option<u64>,
+    pub payer: Option<String>,
+    pub program: Option<String>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct UnwrapSolRequest {
+    pub owner: Option<String>,
+    pub account: Option<String>,
+    pub destination: Option<String>,
+    pub program: Option<String>,
+    #[serde(rename = "multisigSigners")]
+    pub multisig_signers: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct CreateNonceAccountRequest {
+    #[serde(rename = "nonceAccount")]
+    pub nonce_account: Option<String>,
+    pub payer: Option<String>,
+    pub authority: Option<String>,
+    pub lamports: Option<u64>,
+    #[serde(rename = "rentSchedule")]
+    pub rent_schedule: Option<RentScheduleSpec>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct AdvanceNonceAccountRequest {
+    #[serde(rename = "nonceAccount")]
+    pub nonce_account: Option<String>,
+    pub authority: Option<String>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct WithdrawNonceAccountRequest {
+    #[serde(rename = "nonceAccount")]
+    pub nonce_account: Option<String>,
+    pub authority: Option<String>,
+    pub destination: Option<String>,
+    pub lamports: Option<u64>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct AuthorizeNonceAccountRequest {
+    #[serde(rename = "nonceAccount")]
+    pub nonce_account: Option<String>,
+    pub authority: Option<String>,
+    #[serde(rename = "newAuthority")]
+    pub new_authority: Option<String>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct StakeLockupSpec {
+    #[serde(rename = "unixTimestamp")]
+    pub unix_timestamp: Option<i64>,
+    pub epoch: Option<u64>,
+    pub custodian: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateStakeAccountRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: Option<String>,
+    pub payer: Option<String>,
+    pub staker: Option<String>,
+    pub withdrawer: Option<String>,
+    pub lamports: Option<u64>,
+    pub lockup: Option<StakeLockupSpec>,
+    #[serde(rename = "voteAccount")]
+    pub vote_account: Option<String>,
+    #[serde(rename = "rentSchedule")]
+    pub rent_schedule: Option<RentScheduleSpec>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct DelegateStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: Option<String>,
+    pub staker: Option<String>,
+    #[serde(rename = "voteAccount")]
+    pub vote_account: Option<String>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct DeactivateStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: Option<String>,
+    pub staker: Option<String>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct WithdrawStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: Option<String>,
+    pub withdrawer: Option<String>,
+    pub destination: Option<String>,
+    pub lamports: Option<u64>,
+    pub custodian: Option<String>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct SplitStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: Option<String>,
+    pub staker: Option<String>,
+    #[serde(rename = "splitStakeAccount")]
+    pub split_stake_account: Option<String>,
+    pub lamports: Option<u64>,
+    pub payer: Option<String>,
+    #[serde(rename = "rentSchedule")]
+    pub rent_schedule: Option<RentScheduleSpec>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct MergeStakeRequest {
+    #[serde(rename = "destinationStakeAccount")]
+    pub destination_stake_account: Option<String>,
+    #[serde(rename = "sourceStakeAccount")]
+    pub source_stake_account: Option<String>,
+    pub staker: Option<String>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct CreateSystemAccountRequest {
+    pub payer: Option<String>,
+    #[serde(rename = "newAccount")]
+    pub new_account: Option<String>,
+    pub base: Option<String>,
+    pub seed: Option<String>,
+    pub space: Option<u64>,
+    pub owner: Option<String>,
+    pub lamports: Option<u64>,
+    #[serde(rename = "rentSchedule")]
+    pub rent_schedule: Option<RentScheduleSpec>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct AllocateSystemAccountRequest {
+    pub account: Option<String>,
+    pub base: Option<String>,
+    pub seed: Option<String>,
+    pub space: Option<u64>,
+    pub owner: Option<String>,
+    #[serde(rename = "rentSchedule")]
+    pub rent_schedule: Option<RentScheduleSpec>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct AssignSystemAccountRequest {
+    pub account: Option<String>,
+    pub base: Option<String>,
+    pub seed: Option<String>,
+    pub owner: Option<String>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum PdaSeedSpec {
+    Utf8(String),
+    Pubkey(String),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    Hex(String),
+}
+
+#[derive(Deserialize)]
+pub struct DerivePdaRequest {
+    #[serde(rename = "programId")]
+    pub program_id: Option<String>,
+    pub seeds: Option<Vec<PdaSeedSpec>>,
+    pub bump: Option<u8>,
+    pub address: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct InspectAddressRequest {
+    pub address: Option<String>,
+    pub mints: Option<Vec<String>>,
 }
 
 #[derive(Deserialize)]
 pub struct SignMessageRequest {
     pub message: Option<String>,
-    pub secret: Option<String>,
+    pub secret: Option<serde_json::Value>,
 }
 
 #[derive(Deserialize)]
@@ -34,6 +477,25 @@ pub struct SendSolRequest {
     pub from: Option<String>,
     pub to: Option<String>,
     pub lamports: Option<u64>,
+    pub memo: Option<String>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct BatchSolRecipient {
+    pub to: Option<String>,
+    pub lamports: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct SendSolBatchRequest {
+    pub from: Option<String>,
+    #[serde(rename = "feePayer")]
+    pub fee_payer: Option<String>,
+    pub recipients: Option<Vec<BatchSolRecipient>>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
 }
 
 #[derive(Deserialize)]
@@ -41,5 +503,125 @@ pub struct SendTokenRequest {
     pub destination: Option<String>,
     pub mint: Option<String>,
     pub owner: Option<String>,
-    pub amount: Option<u64>,
-} 
\ No newline at end of file
+    pub amount: Option<TokenAmount>,
+    pub decimals: Option<u8>,
+    pub program: Option<String>,
+    #[serde(rename = "createDestinationAta")]
+    pub create_destination_ata: Option<bool>,
+    pub payer: Option<String>,
+    #[serde(rename = "multisigSigners")]
+    pub multisig_signers: Option<Vec<String>>,
+    pub memo: Option<String>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+} 
+
+#[derive(Deserialize)]
+pub struct BatchTokenRecipient {
+    pub destination: Option<String>,
+    pub amount: Option<TokenAmount>,
+}
+
+#[derive(Deserialize)]
+pub struct SendTokenBatchRequest {
+    pub mint: Option<String>,
+    pub owner: Option<String>,
+    pub decimals: Option<u8>,
+    pub program: Option<String>,
+    #[serde(rename = "createDestinationAta")]
+    pub create_destination_ata: Option<bool>,
+    pub payer: Option<String>,
+    #[serde(rename = "feePayer")]
+    pub fee_payer: Option<String>,
+    #[serde(rename = "multisigSigners")]
+    pub multisig_signers: Option<Vec<String>>,
+    pub recipients: Option<Vec<BatchTokenRecipient>>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+pub struct MemoRequest {
+    pub memo: Option<String>,
+    pub signers: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum InstructionSpec {
+    CreateToken(CreateTokenRequest),
+    MintToken(MintTokenRequest),
+    SendSol(SendSolRequest),
+    SendToken(SendTokenRequest),
+    BurnToken(BurnTokenRequest),
+    ApproveToken(ApproveTokenRequest),
+    RevokeToken(RevokeTokenRequest),
+    FreezeAccount(FreezeAccountRequest),
+    ThawAccount(FreezeAccountRequest),
+    CloseAccount(CloseAccountRequest),
+    SetAuthority(SetAuthorityRequest),
+    CreateMultisig(CreateMultisigRequest),
+    WrapSol(WrapSolRequest),
+    UnwrapSol(UnwrapSolRequest),
+    Memo(MemoRequest),
+    CreateNonceAccount(CreateNonceAccountRequest),
+    AdvanceNonceAccount(AdvanceNonceAccountRequest),
+    WithdrawNonceAccount(WithdrawNonceAccountRequest),
+    AuthorizeNonceAccount(AuthorizeNonceAccountRequest),
+    CreateStakeAccount(CreateStakeAccountRequest),
+    DelegateStake(DelegateStakeRequest),
+    DeactivateStake(DeactivateStakeRequest),
+    WithdrawStake(WithdrawStakeRequest),
+    SplitStake(SplitStakeRequest),
+    MergeStake(MergeStakeRequest),
+    CreateSystemAccount(CreateSystemAccountRequest),
+    CreateSystemAccountWithSeed(CreateSystemAccountRequest),
+    AllocateSystemAccount(AllocateSystemAccountRequest),
+    AllocateSystemAccountWithSeed(AllocateSystemAccountRequest),
+    AssignSystemAccount(AssignSystemAccountRequest),
+    AssignSystemAccountWithSeed(AssignSystemAccountRequest),
+    Instruction(RawInstructionSpec),
+}
+
+#[derive(Deserialize)]
+pub struct RawInstructionSpec {
+    pub program_id: Option<String>,
+    pub accounts: Option<Vec<RawAccountMeta>>,
+    pub instruction_data: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RawAccountMeta {
+    pub pubkey: Option<String>,
+    #[serde(default)]
+    pub is_signer: bool,
+    #[serde(default)]
+    pub is_writable: bool,
+}
+
+#[derive(Deserialize)]
+pub struct AddressLookupTableSpec {
+    pub key: Option<String>,
+    pub addresses: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+pub struct BuildTransactionRequest {
+    #[serde(rename = "feePayer")]
+    pub fee_payer: Option<String>,
+    #[serde(rename = "recentBlockhash")]
+    pub recent_blockhash: Option<String>,
+    #[serde(rename = "nonceAccount")]
+    pub nonce_account: Option<String>,
+    #[serde(rename = "nonceAuthority")]
+    pub nonce_authority: Option<String>,
+    pub nonce: Option<String>,
+    pub instructions: Option<Vec<InstructionSpec>>,
+    pub version: Option<String>,
+    #[serde(rename = "addressLookupTables")]
+    pub address_lookup_tables: Option<Vec<AddressLookupTableSpec>>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetSpec,
+}
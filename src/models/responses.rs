@@ -1,5 +1,7 @@
 use serde::Serialize;
 
+use crate::models::SecretKeyFormat;
+
 #[derive(Serialize)]
 pub struct ApiResponse<T> {
     pub success: bool,
@@ -27,10 +29,32 @@ impl<T> ApiResponse<T> {
     }
 }
 
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum SecretKeyOutput {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
 #[derive(Serialize)]
 pub struct KeypairData {
+    pub pubkey: String,
+    pub secret: SecretKeyOutput,
+    pub secret_format: SecretKeyFormat,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct GrindKeypairData {
     pub pubkey: String,
     pub secret: String,
+    pub found: bool,
+    pub matched_characters: usize,
+    pub attempts: u64,
+    pub elapsed_ms: u64,
 }
 
 #[derive(Serialize)]
@@ -38,6 +62,7 @@ pub struct SignMessageData {
     pub signature: String,
     pub pubkey: String,
     pub message: String,
+    pub secret_format: SecretKeyFormat,
 }
 
 #[derive(Serialize)]
@@ -54,11 +79,78 @@ pub struct InstructionData {
     pub instruction_data: String,
 }
 
+#[derive(Serialize)]
+pub struct InstructionBundleData {
+    pub instructions: Vec<InstructionData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_space: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rent_exempt_lamports: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_fee_lamports: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum InstructionOutput<T> {
+    Single(T),
+    Bundle(InstructionBundleData),
+}
+
+#[derive(Serialize)]
+pub struct SetAuthorityData {
+    #[serde(flatten)]
+    pub instruction: InstructionOutput<InstructionData>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct SystemAccountData {
+    #[serde(flatten)]
+    pub instruction: InstructionOutput<InstructionData>,
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_space: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rent_exempt_lamports: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct PdaData {
+    pub address: String,
+    pub bump: u8,
+    pub program_id: String,
+    pub seeds: Vec<String>,
+    pub canonical_bump: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matches_address: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct AssociatedTokenAccountData {
+    pub mint: String,
+    pub token_program: String,
+    pub address: String,
+}
+
+#[derive(Serialize)]
+pub struct AddressInspectionData {
+    pub address: String,
+    pub on_curve: bool,
+    pub address_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub known_program: Option<String>,
+    pub associated_token_accounts: Vec<AssociatedTokenAccountData>,
+    pub warnings: Vec<String>,
+}
+
 #[derive(Serialize)]
 pub struct AccountInfo {
     pub pubkey: String,
     pub is_signer: bool,
     pub is_writable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -66,18 +158,55 @@ pub struct SolTransferData {
     pub program_id: String,
     pub accounts: Vec<String>,
     pub instruction_data: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<Vec<InstructionData>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_fee_lamports: Option<u64>,
 }
 
 #[derive(Serialize)]
-pub struct TokenTransferData {
-    pub program_id: String,
-    pub accounts: Vec<TokenAccountInfo>,
-    pub instruction_data: String,
+pub struct BatchTransactionData {
+    pub instructions: Vec<InstructionData>,
+    pub recipients: Vec<usize>,
+    pub required_signers: Vec<String>,
+    pub size_bytes: usize,
+    pub estimated_fee_lamports: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_fee_lamports: Option<u64>,
 }
 
 #[derive(Serialize)]
-pub struct TokenAccountInfo {
-    pub pubkey: String,
-    #[serde(rename = "isSigner")]
-    pub is_signer: bool,
-} 
\ No newline at end of file
+pub struct BatchRecipientError {
+    pub index: usize,
+    pub recipient: Option<String>,
+    pub error: String,
+}
+
+#[derive(Serialize)]
+pub struct BatchTransferData {
+    pub transactions: Vec<BatchTransactionData>,
+    pub errors: Vec<BatchRecipientError>,
+}
+
+#[derive(Serialize)]
+pub struct TransactionData {
+    pub version: String,
+    pub transaction_base64: String,
+    pub transaction_base58: String,
+    pub message_hash: String,
+    pub required_signers: Vec<String>,
+    pub size_bytes: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address_table_lookups: Option<Vec<AddressTableLookupData>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_fee_lamports: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct AddressTableLookupData {
+    pub table_key: String,
+    pub writable_indexes: Vec<u8>,
+    pub writable_addresses: Vec<String>,
+    pub readonly_indexes: Vec<u8>,
+    pub readonly_addresses: Vec<String>,
+}
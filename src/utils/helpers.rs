@@ -1,27 +1,198 @@
-use solana_program::{instruction::Instruction, pubkey::Pubkey};
-use solana_sdk::signature::Keypair;
+use axum::{http::StatusCode, response::Json as ResponseJson};
+use bip39::{Mnemonic, Seed};
+use solana_program::{
+    hash::Hash,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    rent::{ACCOUNT_STORAGE_OVERHEAD, Rent},
+    system_instruction::MAX_PERMITTED_DATA_LENGTH,
+};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    derivation_path::DerivationPath,
+    signature::Keypair,
+    signer::keypair::keypair_from_seed_and_derivation_path,
+};
 use std::str::FromStr;
 
-use crate::models::{AccountInfo, InstructionData};
+use crate::models::{
+    AccountInfo, ApiResponse, ComputeBudgetSpec, InstructionBundleData, InstructionData, InstructionOutput, RentScheduleSpec,
+    SecretKeyFormat, SecretKeyOutput, TokenAmount,
+};
+
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+const DEFAULT_COMPUTE_UNITS_PER_INSTRUCTION: u32 = 200_000;
+const HARDENED_INDEX_OFFSET: u32 = 1 << 31;
+
+pub fn is_base58_character(character: char) -> bool {
+    character.is_ascii_alphanumeric() && !"0OIl".contains(character)
+}
 
 pub fn parse_pubkey(address_string: &str) -> Result<Pubkey, String> {
     if address_string.len() < 32 || address_string.len() > 44 {
         return Err("The provided address length is not valid for a Solana public key".to_string());
     }
     
-    if !address_string.chars().all(|character| character.is_ascii_alphanumeric() && !"0OIl".contains(character)) {
+    if !address_string.chars().all(is_base58_character) {
         return Err("The address contains invalid characters for base58 encoding".to_string());
     }
     
     Pubkey::from_str(address_string).map_err(|_| format!("Unable to parse the provided address as a valid Solana public key: {}", address_string))
 }
 
+pub fn parse_optional_pubkey(address_string: Option<&str>) -> Result<Option<Pubkey>, String> {
+    match address_string {
+        Some(address) if !address.is_empty() => parse_pubkey(address).map(Some),
+        _ => Ok(None),
+    }
+}
+
+pub fn parse_multisig_signers(signer_addresses: Option<&[String]>) -> Result<Vec<Pubkey>, String> {
+    let signer_addresses = signer_addresses.unwrap_or_default();
+    if signer_addresses.len() > spl_token_2022::instruction::MAX_SIGNERS {
+        return Err(format!("An SPL multisig supports at most {} signers", spl_token_2022::instruction::MAX_SIGNERS));
+    }
+
+    let mut signer_public_keys: Vec<Pubkey> = Vec::with_capacity(signer_addresses.len());
+    for signer_address in signer_addresses {
+        let signer_public_key = parse_pubkey(signer_address)?;
+        if signer_public_keys.contains(&signer_public_key) {
+            return Err(format!("The multisig signer {} is listed more than once", signer_public_key));
+        }
+        signer_public_keys.push(signer_public_key);
+    }
+
+    Ok(signer_public_keys)
+}
+
+pub fn resolve_token_account(
+    account_address: Option<&str>,
+    owner_public_key: &Pubkey,
+    mint_public_key: Option<&Pubkey>,
+    token_program_id: &Pubkey,
+) -> Result<Pubkey, String> {
+    match (parse_optional_pubkey(account_address)?, mint_public_key) {
+        (Some(token_account), _) => Ok(token_account),
+        (None, Some(mint)) => Ok(spl_associated_token_account::get_associated_token_address_with_program_id(owner_public_key, mint, token_program_id)),
+        (None, None) => Err("Please provide either the token account or the mint to derive it from".to_string()),
+    }
+}
+
+pub fn parse_blockhash(blockhash_string: &str) -> Result<Hash, String> {
+    if blockhash_string.len() < 32 || blockhash_string.len() > 44 {
+        return Err("The provided blockhash length is not valid for a Solana blockhash".to_string());
+    }
+
+    Hash::from_str(blockhash_string).map_err(|_| format!("Unable to parse the provided blockhash: {}", blockhash_string))
+}
+
+pub fn parse_token_program(program_name: Option<&str>) -> Result<Pubkey, String> {
+    match program_name {
+        None | Some("spl-token") => Ok(spl_token::id()),
+        Some("token-2022") => Ok(spl_token_2022::id()),
+        Some(_) => Err("The token program must be either \"spl-token\" or \"token-2022\"".to_string()),
+    }
+}
+
+pub fn parse_token_decimals(decimals: Option<u8>) -> Result<Option<u8>, String> {
+    match decimals {
+        Some(decimal_count) if decimal_count > 9 => Err("Decimals must be between 0 and 9".to_string()),
+        other => Ok(other),
+    }
+}
+
+pub fn ui_amount_to_base_units(amount_text: &str, decimals: u8) -> Result<u64, String> {
+    let (whole_digits, fractional_digits) = amount_text.split_once('.').unwrap_or((amount_text, ""));
+
+    if whole_digits.is_empty() && fractional_digits.is_empty() {
+        return Err("The token amount cannot be empty".to_string());
+    }
+
+    if !whole_digits.chars().chain(fractional_digits.chars()).all(|character| character.is_ascii_digit()) {
+        return Err(format!("The token amount \"{}\" is not a valid decimal number", amount_text));
+    }
+
+    if fractional_digits.len() > decimals as usize {
+        return Err(format!("The token amount \"{}\" has more decimal places than the token's {} decimals", amount_text, decimals));
+    }
+
+    let base_unit_digits = format!("{}{:0<width$}", whole_digits, fractional_digits, width = decimals as usize);
+    base_unit_digits
+        .parse::<u64>()
+        .map_err(|_| format!("The token amount \"{}\" is too large", amount_text))
+}
+
+pub fn token_amount_to_base_units(token_amount: &TokenAmount, decimals: Option<u8>) -> Result<u64, String> {
+    match (token_amount, decimals) {
+        (TokenAmount::BaseUnits(base_units), _) => Ok(*base_units),
+        (TokenAmount::Decimal(amount_text), Some(decimal_count)) => ui_amount_to_base_units(amount_text, decimal_count),
+        (TokenAmount::Decimal(_), None) => Err("Please provide the token's decimals to use a decimal amount".to_string()),
+    }
+}
+
+pub fn rent_from_schedule(rent_schedule: Option<&RentScheduleSpec>) -> Result<Rent, String> {
+    let mut rent_parameters = Rent::default();
+    let Some(schedule) = rent_schedule else {
+        return Ok(rent_parameters);
+    };
+
+    if let Some(lamports_per_byte_year) = schedule.lamports_per_byte_year {
+        rent_parameters.lamports_per_byte_year = lamports_per_byte_year;
+    }
+
+    match schedule.exemption_threshold {
+        Some(threshold) if threshold.is_finite() && threshold > 0.0 => rent_parameters.exemption_threshold = threshold,
+        Some(_) => return Err("The rent exemption threshold must be a positive number of years".to_string()),
+        None => {}
+    }
+
+    // Rent::minimum_balance multiplies without overflow checks, so the schedule must fit the largest possible account
+    let largest_account_rent_per_year = (ACCOUNT_STORAGE_OVERHEAD + MAX_PERMITTED_DATA_LENGTH)
+        .checked_mul(rent_parameters.lamports_per_byte_year)
+        .ok_or_else(|| "The rent schedule's lamports per byte-year is too large to compute rent with".to_string())?;
+    if largest_account_rent_per_year as f64 * rent_parameters.exemption_threshold >= u64::MAX as f64 {
+        return Err("The rent schedule would make rent exemption cost more lamports than can exist".to_string());
+    }
+
+    Ok(rent_parameters)
+}
+
+pub fn decode_hex(hex_string: &str) -> Result<Vec<u8>, String> {
+    let hex_digits = hex_string.strip_prefix("0x").unwrap_or(hex_string);
+    if !hex_digits.len().is_multiple_of(2) || !hex_digits.chars().all(|character| character.is_ascii_hexdigit()) {
+        return Err(format!("\"{}\" is not a valid hex string", hex_string));
+    }
+
+    (0..hex_digits.len())
+        .step_by(2)
+        .map(|digit_index| u8::from_str_radix(&hex_digits[digit_index..digit_index + 2], 16).map_err(|_| format!("\"{}\" is not a valid hex string", hex_string)))
+        .collect()
+}
+
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// Derives along m/44'/501'/account'/0', the path used by Phantom, Solflare and `solana-keygen recover`
+pub fn keypair_from_mnemonic(mnemonic_phrase: &Mnemonic, passphrase: &str, account_index: u32) -> Result<(Keypair, String), String> {
+    if account_index >= HARDENED_INDEX_OFFSET {
+        return Err(format!("The account index must be less than {}", HARDENED_INDEX_OFFSET));
+    }
+
+    let mnemonic_seed = Seed::new(mnemonic_phrase, passphrase);
+    let derivation_path = DerivationPath::new_bip44(Some(account_index), Some(0));
+    let derived_keypair = keypair_from_seed_and_derivation_path(mnemonic_seed.as_bytes(), Some(derivation_path))
+        .map_err(|_| "Unable to derive a keypair from the mnemonic".to_string())?;
+
+    Ok((derived_keypair, format!("m/44'/501'/{}'/0'", account_index)))
+}
+
 pub fn keypair_from_base58(private_key_string: &str) -> Result<Keypair, String> {
     if private_key_string.len() < 80 || private_key_string.len() > 100 {
         return Err("The private key length doesn't match expected base58 encoding standards".to_string());
     }
     
-    if !private_key_string.chars().all(|character| character.is_ascii_alphanumeric() && !"0OIl".contains(character)) {
+    if !private_key_string.chars().all(is_base58_character) {
         return Err("The private key contains characters that aren't valid in base58 encoding".to_string());
     }
     
@@ -36,6 +207,64 @@ pub fn keypair_from_base58(private_key_string: &str) -> Result<Keypair, String>
     Keypair::from_bytes(&decoded_key_bytes).map_err(|_| "The provided bytes don't form a valid Solana keypair".to_string())
 }
 
+fn keypair_from_secret_bytes(secret_bytes: &[u8], format_name: &str) -> Result<Keypair, String> {
+    if secret_bytes.len() != 64 {
+        return Err(format!("The secret was read as {} of {} bytes, but Solana keypairs are exactly 64 bytes", format_name, secret_bytes.len()));
+    }
+
+    Keypair::from_bytes(secret_bytes).map_err(|_| format!("The secret was read as {}, but its bytes don't form a valid Solana keypair", format_name))
+}
+
+fn secret_bytes_from_json_array(secret_elements: &[serde_json::Value]) -> Result<Vec<u8>, String> {
+    secret_elements
+        .iter()
+        .map(|secret_element| secret_element.as_u64().and_then(|secret_byte| u8::try_from(secret_byte).ok()))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| "The secret looks like a JSON byte array, but it must contain only numbers from 0 to 255".to_string())
+}
+
+// Strings are checked for a JSON array first, then hex (0x-prefixed or only hex digits), and fall back to base58
+pub fn keypair_from_secret(secret_input: &serde_json::Value) -> Result<(Keypair, SecretKeyFormat), String> {
+    let secret_text = match secret_input {
+        serde_json::Value::Array(secret_elements) => {
+            let secret_bytes = secret_bytes_from_json_array(secret_elements)?;
+            return Ok((keypair_from_secret_bytes(&secret_bytes, "a JSON byte array")?, SecretKeyFormat::ByteArray));
+        }
+        serde_json::Value::String(secret_text) => secret_text.trim(),
+        _ => return Err("The secret must be a base58 string, a JSON byte array or a hex string".to_string()),
+    };
+
+    if secret_text.starts_with('[') {
+        let secret_elements: Vec<serde_json::Value> =
+            serde_json::from_str(secret_text).map_err(|_| "The secret looks like a JSON byte array, but it is not valid JSON".to_string())?;
+        let secret_bytes = secret_bytes_from_json_array(&secret_elements)?;
+        return Ok((keypair_from_secret_bytes(&secret_bytes, "a JSON byte array")?, SecretKeyFormat::ByteArray));
+    }
+
+    let is_bare_hex = !secret_text.is_empty() && secret_text.chars().all(|character| character.is_ascii_hexdigit());
+    if let Some(hex_digits) = secret_text.strip_prefix("0x").or(is_bare_hex.then_some(secret_text)) {
+        if !hex_digits.chars().all(|character| character.is_ascii_hexdigit()) {
+            return Err("The secret looks like hex, but it contains characters that are not hex digits".to_string());
+        }
+        if !hex_digits.len().is_multiple_of(2) {
+            return Err(format!("The secret looks like hex, but it has an odd number of digits ({})", hex_digits.len()));
+        }
+        let secret_bytes = decode_hex(hex_digits)?;
+        return Ok((keypair_from_secret_bytes(&secret_bytes, "hex")?, SecretKeyFormat::Hex));
+    }
+
+    Ok((keypair_from_base58(secret_text)?, SecretKeyFormat::Base58))
+}
+
+pub fn encode_secret_key(wallet_keypair: &Keypair, secret_format: SecretKeyFormat) -> SecretKeyOutput {
+    let secret_bytes = wallet_keypair.to_bytes();
+    match secret_format {
+        SecretKeyFormat::Base58 => SecretKeyOutput::Text(bs58::encode(&secret_bytes).into_string()),
+        SecretKeyFormat::ByteArray => SecretKeyOutput::Bytes(secret_bytes.to_vec()),
+        SecretKeyFormat::Hex => SecretKeyOutput::Text(encode_hex(&secret_bytes)),
+    }
+}
+
 pub fn instruction_to_response(blockchain_instruction: Instruction) -> InstructionData {
     let account_information = blockchain_instruction
         .accounts
@@ -44,6 +273,7 @@ pub fn instruction_to_response(blockchain_instruction: Instruction) -> Instructi
             pubkey: account_metadata.pubkey.to_string(),
             is_signer: account_metadata.is_signer,
             is_writable: account_metadata.is_writable,
+            role: None,
         })
         .collect();
 
@@ -52,4 +282,236 @@ pub fn instruction_to_response(blockchain_instruction: Instruction) -> Instructi
         accounts: account_information,
         instruction_data: bs58::encode(&blockchain_instruction.data).into_string(),
     }
-} 
\ No newline at end of file
+}
+
+/// Prepends the ComputeBudget instructions and returns the priority fee they add, if any were requested.
+/// Without an explicit limit the fee assumes the runtime default of 200k units per instruction.
+pub fn prepend_compute_budget_instructions(
+    instructions: Vec<Instruction>,
+    compute_budget: &ComputeBudgetSpec,
+) -> Result<(Vec<Instruction>, Option<u64>), String> {
+    if compute_budget.compute_unit_limit.is_none() && compute_budget.compute_unit_price_micro_lamports.is_none() {
+        return Ok((instructions, None));
+    }
+
+    let compute_unit_limit = match compute_budget.compute_unit_limit {
+        Some(0) => return Err("The compute unit limit must be greater than 0".to_string()),
+        Some(limit) if limit > MAX_COMPUTE_UNIT_LIMIT => {
+            return Err(format!("The compute unit limit cannot exceed {} units", MAX_COMPUTE_UNIT_LIMIT));
+        }
+        Some(limit) => limit,
+        None => (instructions.len() as u32).saturating_mul(DEFAULT_COMPUTE_UNITS_PER_INSTRUCTION).min(MAX_COMPUTE_UNIT_LIMIT),
+    };
+
+    let compute_unit_price = compute_budget.compute_unit_price_micro_lamports.unwrap_or(0);
+    let priority_fee_lamports = (compute_unit_limit as u128 * compute_unit_price as u128).div_ceil(1_000_000);
+    let priority_fee_lamports = u64::try_from(priority_fee_lamports)
+        .map_err(|_| "The compute unit price results in an unreasonably large priority fee".to_string())?;
+
+    let mut budgeted_instructions = Vec::with_capacity(instructions.len() + 2);
+    if let Some(limit) = compute_budget.compute_unit_limit {
+        budgeted_instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+    }
+    if let Some(price) = compute_budget.compute_unit_price_micro_lamports {
+        budgeted_instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+    }
+    budgeted_instructions.extend(instructions);
+
+    Ok((budgeted_instructions, Some(priority_fee_lamports)))
+}
+
+pub fn instructions_to_bundle(
+    instructions: Vec<Instruction>,
+    compute_budget: &ComputeBudgetSpec,
+    bundle_response: impl Fn(Instruction) -> InstructionData,
+) -> Result<InstructionBundleData, String> {
+    let (budgeted_instructions, priority_fee_lamports) = prepend_compute_budget_instructions(instructions, compute_budget)?;
+
+    Ok(InstructionBundleData {
+        instructions: budgeted_instructions.into_iter().map(bundle_response).collect(),
+        account_space: None,
+        rent_exempt_lamports: None,
+        priority_fee_lamports,
+    })
+}
+
+pub fn instructions_to_output<T>(
+    mut instructions: Vec<Instruction>,
+    compute_budget: &ComputeBudgetSpec,
+    single_response: impl FnOnce(Instruction) -> T,
+    bundle_response: impl Fn(Instruction) -> InstructionData,
+) -> Result<InstructionOutput<T>, String> {
+    let has_compute_budget = compute_budget.compute_unit_limit.is_some() || compute_budget.compute_unit_price_micro_lamports.is_some();
+    if instructions.len() == 1 && !has_compute_budget {
+        return Ok(InstructionOutput::Single(single_response(instructions.remove(0))));
+    }
+
+    instructions_to_bundle(instructions, compute_budget, bundle_response).map(InstructionOutput::Bundle)
+}
+
+pub fn result_to_response<T>(handler_result: Result<T, String>) -> (StatusCode, ResponseJson<ApiResponse<T>>) {
+    match handler_result {
+        Ok(response_data) => (StatusCode::OK, ResponseJson(ApiResponse::success(response_data))),
+        Err(validation_error) => (StatusCode::BAD_REQUEST, ResponseJson(ApiResponse::error(validation_error))),
+    }
+}
+
+pub fn instructions_result_to_response(
+    instructions_result: Result<Vec<Instruction>, String>,
+    compute_budget: &ComputeBudgetSpec,
+) -> (StatusCode, ResponseJson<ApiResponse<InstructionOutput<InstructionData>>>) {
+    result_to_response(instructions_result.and_then(|created_instructions| {
+        instructions_to_output(created_instructions, compute_budget, instruction_to_response, instruction_to_response)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bip39::Language;
+    use solana_sdk::signature::Signer;
+
+    fn rent_schedule(lamports_per_byte_year: Option<u64>, exemption_threshold: Option<f64>) -> RentScheduleSpec {
+        RentScheduleSpec { lamports_per_byte_year, exemption_threshold }
+    }
+
+    #[test]
+    fn ui_amount_converts_decimal_strings_exactly() {
+        assert_eq!(ui_amount_to_base_units("12.5", 2), Ok(1_250));
+        assert_eq!(ui_amount_to_base_units(".5", 1), Ok(5));
+        assert_eq!(ui_amount_to_base_units("12.", 2), Ok(1_200));
+        assert_eq!(ui_amount_to_base_units("12", 0), Ok(12));
+        assert_eq!(ui_amount_to_base_units("0.000000001", 9), Ok(1));
+        assert_eq!(ui_amount_to_base_units("0.1", 9), Ok(100_000_000));
+        assert_eq!(ui_amount_to_base_units("18446744073709551615", 0), Ok(u64::MAX));
+        assert_eq!(ui_amount_to_base_units("18446744073.709551615", 9), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn ui_amount_rejects_malformed_amounts() {
+        for malformed_amount in [".", "", "1.2.3", "-1", "+1", "1e3", " 1", "1,5", "0x10"] {
+            assert!(ui_amount_to_base_units(malformed_amount, 6).is_err(), "{:?} should be rejected", malformed_amount);
+        }
+    }
+
+    #[test]
+    fn ui_amount_rejects_extra_decimal_places() {
+        assert!(ui_amount_to_base_units("1.234", 2).is_err());
+        assert!(ui_amount_to_base_units(".5", 0).is_err());
+        assert_eq!(ui_amount_to_base_units("1.230", 3), Ok(1_230));
+    }
+
+    #[test]
+    fn ui_amount_rejects_u64_overflow() {
+        assert!(ui_amount_to_base_units("18446744073709551616", 0).is_err());
+        assert!(ui_amount_to_base_units("18446744073.709551616", 9).is_err());
+        assert!(ui_amount_to_base_units("18446744074", 9).is_err());
+    }
+
+    #[test]
+    fn decimal_token_amounts_need_decimals() {
+        assert_eq!(token_amount_to_base_units(&TokenAmount::BaseUnits(7), None), Ok(7));
+        assert_eq!(token_amount_to_base_units(&TokenAmount::Decimal("1.5".to_string()), Some(6)), Ok(1_500_000));
+        assert!(token_amount_to_base_units(&TokenAmount::Decimal("1.5".to_string()), None).is_err());
+    }
+
+    #[test]
+    fn rent_schedule_defaults_to_the_cluster_rent() {
+        assert_eq!(rent_from_schedule(None).unwrap().minimum_balance(0), Rent::default().minimum_balance(0));
+        assert_eq!(rent_from_schedule(Some(&rent_schedule(None, None))).unwrap().minimum_balance(82), 1_461_600);
+    }
+
+    #[test]
+    fn rent_schedule_rejects_values_that_overflow_minimum_balance() {
+        assert!(rent_from_schedule(Some(&rent_schedule(Some(u64::MAX), None))).is_err());
+        assert!(rent_from_schedule(Some(&rent_schedule(Some(100_000_000_000_000_000), None))).is_err());
+        assert!(rent_from_schedule(Some(&rent_schedule(Some(1), Some(1e300)))).is_err());
+        assert!(rent_from_schedule(Some(&rent_schedule(Some(1), Some(0.0)))).is_err());
+    }
+
+    #[test]
+    fn rent_schedule_accepts_the_largest_safe_rate() {
+        let largest_account = (ACCOUNT_STORAGE_OVERHEAD + MAX_PERMITTED_DATA_LENGTH) as usize;
+        let rent = rent_from_schedule(Some(&rent_schedule(Some(1_000_000), Some(2.0)))).unwrap();
+        assert_eq!(rent.minimum_balance(largest_account - ACCOUNT_STORAGE_OVERHEAD as usize), largest_account as u64 * 2_000_000);
+    }
+
+    const TEST_MNEMONIC: &str = "pill tomorrow foster begin walnut borrow virtual kick shift mutual shoe scatter";
+
+    fn assert_secret_parses_as(secret_input: serde_json::Value, expected_keypair: &Keypair, expected_format: SecretKeyFormat) {
+        let (parsed_keypair, secret_format) = keypair_from_secret(&secret_input).unwrap_or_else(|parse_error| panic!("{}: {}", secret_input, parse_error));
+        assert_eq!(parsed_keypair.to_bytes(), expected_keypair.to_bytes());
+        assert_eq!(serde_json::to_value(secret_format).unwrap(), serde_json::to_value(expected_format).unwrap());
+    }
+
+    fn secret_error(secret_input: serde_json::Value) -> String {
+        keypair_from_secret(&secret_input).err().unwrap()
+    }
+
+    #[test]
+    fn secret_format_is_detected_from_its_shape() {
+        let wallet_keypair = Keypair::new();
+        let secret_bytes = wallet_keypair.to_bytes().to_vec();
+        let secret_hex = encode_hex(&secret_bytes);
+
+        assert_secret_parses_as(serde_json::json!(secret_bytes), &wallet_keypair, SecretKeyFormat::ByteArray);
+        assert_secret_parses_as(serde_json::json!(serde_json::to_string(&secret_bytes).unwrap()), &wallet_keypair, SecretKeyFormat::ByteArray);
+        assert_secret_parses_as(serde_json::json!(secret_hex), &wallet_keypair, SecretKeyFormat::Hex);
+        assert_secret_parses_as(serde_json::json!(format!("0x{}", secret_hex)), &wallet_keypair, SecretKeyFormat::Hex);
+        assert_secret_parses_as(serde_json::json!(wallet_keypair.to_base58_string()), &wallet_keypair, SecretKeyFormat::Base58);
+    }
+
+    #[test]
+    fn secret_byte_arrays_reject_values_outside_a_byte() {
+        for secret_input in [serde_json::json!([1, 2, 300]), serde_json::json!([1, -2]), serde_json::json!("[1, 2.5]"), serde_json::json!(["a"])] {
+            assert!(secret_error(secret_input).contains("only numbers from 0 to 255"));
+        }
+        assert!(secret_error(serde_json::json!([1, 2, 3])).contains("of 3 bytes"));
+        assert!(secret_error(serde_json::json!("[1, 2")).contains("not valid JSON"));
+    }
+
+    #[test]
+    fn secret_hex_errors_describe_the_hex_that_was_sent() {
+        assert!(secret_error(serde_json::json!("deadbeef")).contains("read as hex of 4 bytes"));
+        assert!(secret_error(serde_json::json!("0xdeadbee")).contains("odd number of digits (7)"));
+        assert!(secret_error(serde_json::json!("abc")).contains("odd number of digits (3)"));
+        assert!(secret_error(serde_json::json!("0xnothex")).contains("not hex digits"));
+    }
+
+    #[test]
+    fn secret_must_be_a_string_or_an_array() {
+        for secret_input in [serde_json::json!(42), serde_json::json!(true), serde_json::json!({ "secret": "key" })] {
+            assert_eq!(secret_error(secret_input), "The secret must be a base58 string, a JSON byte array or a hex string");
+        }
+    }
+
+    fn derived_address(passphrase: &str, account_index: u32) -> (String, String) {
+        let test_mnemonic = Mnemonic::from_phrase(TEST_MNEMONIC, Language::English).unwrap();
+        let (derived_keypair, derivation_path) = keypair_from_mnemonic(&test_mnemonic, passphrase, account_index).unwrap();
+        (derived_keypair.pubkey().to_string(), derivation_path)
+    }
+
+    // Same addresses Phantom and `solana-keygen recover 'prompt://?key=<account>/0'` derive for this phrase
+    #[test]
+    fn mnemonic_derives_the_wallet_addresses_for_each_account() {
+        assert_eq!(
+            derived_address("", 0),
+            ("5F86TNSTre3CYwZd1wELsGQGhqG2HkN3d8zxhbyBSnzm".to_string(), "m/44'/501'/0'/0'".to_string())
+        );
+        assert_eq!(
+            derived_address("", 1),
+            ("AWjbG5SH5VEay5ksZbGHHgJhYRhM1rsN5Z538cfFvs4a".to_string(), "m/44'/501'/1'/0'".to_string())
+        );
+    }
+
+    #[test]
+    fn mnemonic_passphrase_changes_the_derived_address() {
+        assert_eq!(derived_address("superdev", 0).0, "8sFvstS5SLNveD8XmHqRK8v5duhxyCKfDVu18uc2Pwru");
+    }
+
+    #[test]
+    fn mnemonic_rejects_hardened_account_indexes() {
+        let test_mnemonic = Mnemonic::from_phrase(TEST_MNEMONIC, Language::English).unwrap();
+        assert!(keypair_from_mnemonic(&test_mnemonic, "", HARDENED_INDEX_OFFSET).is_err());
+    }
+}